@@ -0,0 +1,280 @@
+//! The `batch` subcommand, like `top -b`: print a plain-text summary and process table
+//! every interval, for logging to a file where no terminal is available.
+
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use color_eyre::Result;
+use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+
+use crate::{
+    cli::BatchArgs,
+    format,
+    host::HostInfo,
+    process::{self, ProcessInfo},
+    sampler::{Sample, Sampler},
+};
+
+/// A process table column that can be picked with `--columns`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Column {
+    Pid,
+    Ppid,
+    User,
+    Cpu,
+    /// Resident memory
+    Mem,
+    /// Virtual memory
+    Virt,
+    Read,
+    Write,
+    /// Time the process has been running
+    Time,
+    State,
+    Name,
+    /// Full command line, falling back to the name; cut short unless it is the last column
+    Command,
+}
+
+/// Columns shown when `--columns` is not given.
+pub(crate) const DEFAULT_COLUMNS: &[Column] = &[
+    Column::Pid,
+    Column::User,
+    Column::Cpu,
+    Column::Mem,
+    Column::Command,
+];
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Pid => "PID",
+            Column::Ppid => "PPID",
+            Column::User => "USER",
+            Column::Cpu => "CPU%",
+            Column::Mem => "RSS",
+            Column::Virt => "VIRT",
+            Column::Read => "READ/s",
+            Column::Write => "WRITE/s",
+            Column::Time => "TIME",
+            Column::State => "STATE",
+            Column::Name => "NAME",
+            Column::Command => "COMMAND",
+        }
+    }
+
+    /// Minimum width; numbers are right-aligned within it and text left-aligned.
+    fn width(self) -> usize {
+        match self {
+            Column::Pid | Column::Ppid => 8,
+            Column::User | Column::State => 10,
+            Column::Cpu => 7,
+            Column::Mem | Column::Virt | Column::Time => 10,
+            Column::Read | Column::Write => 12,
+            Column::Name => 16,
+            Column::Command => 32,
+        }
+    }
+
+    fn is_text(self) -> bool {
+        matches!(
+            self,
+            Column::User | Column::State | Column::Name | Column::Command
+        )
+    }
+
+    fn value(self, process: &ProcessInfo) -> String {
+        match self {
+            Column::Pid => process.pid.to_string(),
+            Column::Ppid => process
+                .parent
+                .map_or_else(|| "-".to_string(), |pid| pid.to_string()),
+            Column::User => process.user.clone(),
+            Column::Cpu => format::percent(process.cpu_usage.into()),
+            Column::Mem => format::bytes(process.memory),
+            Column::Virt => format::bytes(process.virtual_memory),
+            Column::Read => format::rate(process.read_rate),
+            Column::Write => format::rate(process.write_rate),
+            Column::Time => format::duration(Duration::from_secs(process.run_time)),
+            Column::State => process.status.clone(),
+            Column::Name => process.name.clone(),
+            Column::Command if process.command.is_empty() => process.name.clone(),
+            // Arguments may contain newlines, which would break the table apart.
+            Column::Command => process.command.replace(char::is_control, " "),
+        }
+    }
+}
+
+/// Print a report every `interval` until `args.iterations` have been printed, or
+/// forever when no count is given.
+pub(crate) fn run(args: &BatchArgs, interval: Duration) -> Result<()> {
+    ignore_broken_pipe(write_reports(args, interval, &mut io::stdout().lock()))
+}
+
+fn write_reports(args: &BatchArgs, interval: Duration, out: &mut impl Write) -> io::Result<()> {
+    let filter = args.processes.filter();
+    let mut sampler = Sampler::new();
+    let mut iteration = 0;
+    while args
+        .iterations
+        .is_none_or(|iterations| iteration < iterations.get())
+    {
+        thread::sleep(interval.max(MINIMUM_CPU_UPDATE_INTERVAL));
+        let mut sample = sampler.sample();
+        process::keep_busiest(&mut sample.processes, &filter, Some(args.top));
+        if iteration > 0 {
+            writeln!(out)?;
+        }
+        write!(out, "{}", report(&sampler.host(), &sample, &args.columns))?;
+        // Flush every report so `nohup ... > log` is never behind by a buffer.
+        out.flush()?;
+        iteration += 1;
+    }
+    Ok(())
+}
+
+/// Treat the reader going away, e.g. `batch | head -20`, as a normal end of output.
+pub(crate) fn ignore_broken_pipe(result: io::Result<()>) -> Result<()> {
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Time, host, CPU and memory lines followed by a table of the sample's processes.
+pub(crate) fn report(host: &HostInfo, sample: &Sample, columns: &[Column]) -> String {
+    let (cpu, memory) = (&sample.cpu, &sample.memory);
+    let [one, five, fifteen] = host.load_average;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "time  {}",
+        format::timestamp(sample.timestamp_ms / 1000)
+    );
+    let _ = writeln!(
+        out,
+        "host  {} ({}, kernel {}), up {}, load {one:.2} {five:.2} {fifteen:.2}",
+        host.host_name,
+        host.os,
+        host.kernel,
+        format::duration(host.uptime),
+    );
+    let _ = writeln!(
+        out,
+        "cpu   {} of {} cores",
+        format::percent(cpu.global_usage.into()),
+        cpu.cores.len()
+    );
+    let _ = writeln!(
+        out,
+        "mem   {} / {} used, {} available",
+        format::bytes(memory.used),
+        format::bytes(memory.total),
+        format::bytes(memory.available)
+    );
+    let _ = writeln!(
+        out,
+        "swap  {} / {} used",
+        format::bytes(memory.swap_used),
+        format::bytes(memory.swap_total)
+    );
+    let _ = writeln!(out);
+    let header = table_row(columns, |column| column.title().to_string());
+    let _ = writeln!(out, "{header}");
+    for process in &sample.processes {
+        let row = table_row(columns, |column| column.value(process));
+        let _ = writeln!(out, "{row}");
+    }
+    out
+}
+
+fn table_row(columns: &[Column], cell: impl Fn(Column) -> String) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(position, &column)| {
+            let (mut value, width) = (cell(column), column.width());
+            // Like `top`, cut long text to the column and mark it with `+`. A command line
+            // in the last column may run on, since nothing follows it.
+            let runs_on = column == Column::Command && position + 1 == columns.len();
+            if !runs_on && value.chars().count() > width {
+                value = value.chars().take(width - 1).chain(['+']).collect();
+            }
+            if column.is_text() {
+                format!("{value:<width$}")
+            } else {
+                format!("{value:>width$}")
+            }
+        })
+        .collect();
+    cells.join(" ").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_time_host_usage_and_processes() {
+        let text = report(&HostInfo::example(), &Sample::example(), DEFAULT_COLUMNS);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "time  2024-03-01 09:30:00 UTC");
+        assert_eq!(
+            lines[1],
+            "host  box (Linux, kernel 6.1), up 00:01:30, load 0.50 0.25 0.00"
+        );
+        assert_eq!(lines[2], "cpu   12.5% of 0 cores");
+        assert_eq!(lines[3], "mem   1.0 KiB / 2.0 KiB used, 1.0 KiB available");
+        assert_eq!(lines[6], "     PID USER          CPU%        RSS COMMAND");
+        assert_eq!(lines[7], "      30 root         40.0%    1.0 KiB bash");
+    }
+
+    #[test]
+    fn columns_follow_the_requested_order() {
+        let columns = [Column::Name, Column::Ppid, Column::Time];
+        let text = report(&HostInfo::example(), &Sample::example(), &columns);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[6], "NAME                 PPID       TIME");
+        assert_eq!(lines[7], "bash                    1   00:01:01");
+    }
+
+    #[test]
+    fn long_values_are_cut_and_commands_kept_on_one_line() {
+        let mut sample = Sample::example();
+        sample.processes[0].name = "pool_workqueue_release".to_string();
+        sample.processes[0].command = "sh -c 'echo a\necho b'".to_string();
+        let text = report(
+            &HostInfo::example(),
+            &sample,
+            &[Column::Name, Column::Command],
+        );
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[7], "pool_workqueue_+ sh -c 'echo a echo b'");
+    }
+
+    #[test]
+    fn commands_before_other_columns_are_cut_to_width() {
+        let mut sample = Sample::example();
+        sample.processes[0].command = "/usr/bin/python3 -m http.server --bind ::".to_string();
+        let text = report(
+            &HostInfo::example(),
+            &sample,
+            &[Column::Pid, Column::Command, Column::Cpu],
+        );
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[6],
+            "     PID COMMAND                             CPU%"
+        );
+        assert_eq!(
+            lines[7],
+            "      30 /usr/bin/python3 -m http.server+   40.0%"
+        );
+    }
+}
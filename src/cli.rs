@@ -0,0 +1,217 @@
+use std::{env, net::SocketAddr, num::NonZeroU64, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::{batch::Column, format::UnitSystem, process::ProcessFilter, tabs::Tab};
+
+/// Command-line arguments.
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Options for the dashboard when no subcommand is given
+    #[command(flatten)]
+    pub tui: TuiArgs,
+
+    /// Unit system for byte values: `iec` (KiB, MiB, ...) or `si` (kB, MB, ...)
+    #[arg(long, global = true, value_name = "SYSTEM")]
+    pub units: Option<UnitSystem>,
+
+    /// Decimal places shown for scaled values such as sizes and percentages
+    #[arg(long, global = true, value_name = "DIGITS", value_parser = clap::value_parser!(u8).range(0..=6))]
+    pub precision: Option<u8>,
+
+    /// Config file to use instead of $XDG_CONFIG_HOME/ratatui-system-monitor/config.toml
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Print the default configuration and exit
+    #[arg(long)]
+    pub print_default_config: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum Command {
+    /// Run the interactive dashboard (the default)
+    Tui(TuiArgs),
+    /// Sample the system and print the results as text, JSON or NDJSON
+    Snapshot(SnapshotArgs),
+    /// Print a summary and process table every interval, like `top -b`
+    Batch(BatchArgs),
+    /// Serve metrics for Prometheus at http://ADDR/metrics in OpenMetrics format
+    Exporter(ExporterArgs),
+    /// Write samples to FILE for replaying later with `--replay FILE`
+    ///
+    /// Each sample takes a few kilobytes, mostly the process list, which comes to roughly
+    /// 50 MB an hour with the default interval and `--top`. Lower `--top` or raise
+    /// `--interval` for a smaller file.
+    Record(RecordArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct TuiArgs {
+    /// Sampling interval in milliseconds, overriding the config file
+    #[arg(short, long, value_name = "MS", value_parser = clap::value_parser!(u64).range(100..=60_000))]
+    pub interval: Option<u64>,
+
+    /// Tab shown at startup
+    #[arg(long, value_enum, default_value_t = Tab::Overview)]
+    pub tab: Tab,
+
+    #[command(flatten)]
+    pub processes: ProcessArgs,
+
+    /// When to use colors; `auto` turns them off if NO_COLOR is set
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorMode::Auto)]
+    pub color: ColorMode,
+
+    /// TOML file describing which panels the overview shows and where, overriding the
+    /// config file's `[layout]`
+    #[arg(long, value_name = "FILE")]
+    pub layout: Option<PathBuf>,
+
+    /// Play back a file written by the `record` subcommand instead of watching this machine
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct SnapshotArgs {
+    /// Milliseconds between samples; CPU usage and rates are measured over this span
+    #[arg(short, long, value_name = "MS", value_parser = clap::value_parser!(u64).range(100..=60_000))]
+    pub interval: Option<u64>,
+
+    /// Number of samples to take
+    #[arg(short = 'n', long, value_name = "N", default_value = "1")]
+    pub count: NonZeroU64,
+
+    /// Output format; `json` prints an array when more than one sample is taken
+    #[arg(long, value_enum, default_value_t = SnapshotFormat::Text)]
+    pub format: SnapshotFormat,
+
+    /// Number of processes to list, busiest first [default: 10 for text, all otherwise]
+    #[arg(long, value_name = "N")]
+    pub top: Option<usize>,
+
+    #[command(flatten)]
+    pub processes: ProcessArgs,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct BatchArgs {
+    /// Milliseconds between reports, overriding the config file
+    #[arg(short, long, value_name = "MS", value_parser = clap::value_parser!(u64).range(100..=60_000))]
+    pub interval: Option<u64>,
+
+    /// Stop after N reports instead of running until interrupted
+    #[arg(short = 'n', long, value_name = "N")]
+    pub iterations: Option<NonZeroU64>,
+
+    /// Number of processes to list, busiest first
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub top: usize,
+
+    /// Process table columns, in order, separated by commas
+    #[arg(
+        short = 'o',
+        long,
+        value_enum,
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        default_value = "pid,user,cpu,mem,command"
+    )]
+    pub columns: Vec<Column>,
+
+    #[command(flatten)]
+    pub processes: ProcessArgs,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct ExporterArgs {
+    /// Address to listen on; use 0.0.0.0 to accept scrapes from other machines
+    #[arg(short, long, value_name = "ADDR", default_value = "127.0.0.1:9184")]
+    pub listen: SocketAddr,
+
+    /// Milliseconds between samples, overriding the config file
+    #[arg(short, long, value_name = "MS", value_parser = clap::value_parser!(u64).range(100..=60_000))]
+    pub interval: Option<u64>,
+
+    /// Also export CPU and memory of the N busiest processes
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub top: usize,
+
+    #[command(flatten)]
+    pub processes: ProcessArgs,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct RecordArgs {
+    /// File to write; an existing file is replaced
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Milliseconds between samples, overriding the config file
+    #[arg(short, long, value_name = "MS", value_parser = clap::value_parser!(u64).range(100..=60_000))]
+    pub interval: Option<u64>,
+
+    /// Stop after N samples instead of recording until interrupted
+    #[arg(short = 'n', long, value_name = "N")]
+    pub count: Option<NonZeroU64>,
+
+    /// Keep only the N busiest processes of each sample. Each one adds a few hundred
+    /// bytes per sample, most of it the command line
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    pub top: usize,
+
+    #[command(flatten)]
+    pub processes: ProcessArgs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum SnapshotFormat {
+    /// A human-readable summary
+    Text,
+    /// One pretty-printed JSON document
+    Json,
+    /// One compact JSON object per line, written as each sample is taken
+    Ndjson,
+}
+
+/// Which processes to list.
+#[derive(Debug, Args)]
+pub(crate) struct ProcessArgs {
+    /// Only list processes whose name or command line contains TEXT (case-insensitive)
+    #[arg(short, long, value_name = "TEXT")]
+    pub filter: Option<String>,
+
+    /// Only list these processes; repeat the flag or separate PIDs with commas
+    #[arg(short, long = "pid", value_name = "PID", value_delimiter = ',')]
+    pub pids: Vec<u32>,
+}
+
+impl ProcessArgs {
+    pub(crate) fn filter(&self) -> ProcessFilter {
+        ProcessFilter::new(self.filter.as_deref(), &self.pids)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// Resolve `auto` following <https://no-color.org>: any non-empty NO_COLOR disables
+    /// colors.
+    pub(crate) fn enabled(self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()),
+        }
+    }
+}
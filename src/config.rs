@@ -0,0 +1,325 @@
+//! Settings read from `config.toml`.
+//!
+//! The file is looked up at `$XDG_CONFIG_HOME/ratatui-system-monitor/config.toml`
+//! (falling back to `~/.config`) unless `--config` names one. Every key is optional; a
+//! missing file means all defaults. Command-line flags override the file.
+
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::{
+    cli::{Cli, TuiArgs},
+    dashboard::{Dashboard, LayoutSpec},
+    format::{Format, UnitSystem},
+    theme::Theme,
+};
+
+/// The configuration with every default spelled out, as printed by
+/// `--print-default-config`.
+pub(crate) const DEFAULT_CONFIG: &str = r##"# ratatui-system-monitor configuration
+
+# How often system statistics are sampled, in milliseconds (100-60000).
+refresh_ms = 500
+
+# Longest time between redraws when there is no input, in milliseconds (10-1000).
+frame_ms = 100
+
+# Byte units: "iec" (KiB, MiB, ...) or "si" (kB, MB, ...).
+units = "iec"
+
+# Decimal places for sizes, rates and percentages (0-6).
+precision = 1
+
+# Colors are names ("red", "lightblue", ...), 0-255 palette indexes or "#rrggbb".
+[colors]
+border = "red"
+low = "green"
+medium = "yellow"
+high = "red"
+cpu = "cyan"
+memory = "green"
+rx = "green"
+tx = "blue"
+read = "green"
+write = "magenta"
+selection = "blue"
+
+# Replace the built-in overview layouts with your own. Each node is either a `widget`
+# (memory, cpu, cpu-history, memory-history, network, disks, disk-io, sensors,
+# processes) or a split into `children`, with an optional `size` ("30%", "1/3",
+# "min 10", "max 40", "fill 2" or a number of cells). Nested splits alternate between
+# rows and columns unless `direction` is "rows" or "columns".
+#
+# [[layout.children]]
+# size = "40%"
+#   [[layout.children.children]]
+#   widget = "cpu"
+#   [[layout.children.children]]
+#   widget = "cpu-history"
+#   size = "2/3"
+#
+# [[layout.children]]
+# widget = "processes"
+"##;
+
+#[derive(Debug)]
+pub(crate) struct Config {
+    pub refresh_interval: Duration,
+    pub frame_interval: Duration,
+    pub format: Format,
+    pub theme: Theme,
+    /// Overview layout replacing the built-in ones.
+    pub layout: Option<Dashboard>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_millis(500),
+            frame_interval: Duration::from_millis(100),
+            format: Format::default(),
+            theme: Theme::default(),
+            layout: None,
+        }
+    }
+}
+
+/// The file as written, before validation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    refresh_ms: Option<u64>,
+    frame_ms: Option<u64>,
+    units: Option<String>,
+    precision: Option<u64>,
+    colors: RawColors,
+    layout: Option<LayoutSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawColors {
+    border: Option<String>,
+    low: Option<String>,
+    medium: Option<String>,
+    high: Option<String>,
+    cpu: Option<String>,
+    memory: Option<String>,
+    rx: Option<String>,
+    tx: Option<String>,
+    read: Option<String>,
+    write: Option<String>,
+    selection: Option<String>,
+}
+
+impl Config {
+    /// Load `path`, or the default location when it is `None`. Only an explicitly named
+    /// file has to exist.
+    pub(crate) fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if !required && err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(err) => {
+                return Err(err)
+                    .wrap_err_with(|| format!("could not read config file {}", path.display()));
+            }
+        };
+        Self::parse(&contents).wrap_err_with(|| format!("invalid config file {}", path.display()))
+    }
+
+    pub(crate) fn parse(contents: &str) -> Result<Self> {
+        let raw: RawConfig = toml::from_str(contents)?;
+        let defaults = Self::default();
+        let refresh_ms = ranged("refresh_ms", raw.refresh_ms, 100..=60_000)?;
+        let frame_ms = ranged("frame_ms", raw.frame_ms, 10..=1000)?;
+        let precision = ranged("precision", raw.precision, 0..=6)?;
+        let units = raw
+            .units
+            .map(|units| UnitSystem::from_str(&units).map_err(|err| eyre!("units: {err}")))
+            .transpose()?;
+        Ok(Self {
+            refresh_interval: refresh_ms.map_or(defaults.refresh_interval, Duration::from_millis),
+            frame_interval: frame_ms.map_or(defaults.frame_interval, Duration::from_millis),
+            format: Format {
+                units: units.unwrap_or(defaults.format.units),
+                precision: precision.map_or(defaults.format.precision, |p| p as usize),
+            },
+            theme: raw.colors.theme(defaults.theme)?,
+            layout: raw.layout.as_ref().map(Dashboard::from_spec).transpose()?,
+        })
+    }
+
+    /// Let command-line flags take precedence over the file.
+    pub(crate) fn apply_cli(&mut self, cli: &Cli) {
+        if let Some(units) = cli.units {
+            self.format.units = units;
+        }
+        if let Some(precision) = cli.precision {
+            self.format.precision = precision.into();
+        }
+    }
+
+    /// Let the dashboard's own flags take precedence over the file.
+    pub(crate) fn apply_tui_args(&mut self, args: &TuiArgs) -> Result<()> {
+        if let Some(interval) = args.interval {
+            self.refresh_interval = Duration::from_millis(interval);
+        }
+        if let Some(path) = &args.layout {
+            self.layout = Some(Dashboard::load(path)?);
+        }
+        Ok(())
+    }
+}
+
+impl RawColors {
+    fn theme(&self, defaults: Theme) -> Result<Theme> {
+        let color = |name: &str, value: &Option<String>, default: Color| match value {
+            None => Ok(default),
+            Some(value) => {
+                Color::from_str(value).map_err(|_| eyre!("colors.{name}: unknown color `{value}`"))
+            }
+        };
+        Ok(Theme {
+            border: color("border", &self.border, defaults.border)?,
+            low: color("low", &self.low, defaults.low)?,
+            medium: color("medium", &self.medium, defaults.medium)?,
+            high: color("high", &self.high, defaults.high)?,
+            cpu: color("cpu", &self.cpu, defaults.cpu)?,
+            memory: color("memory", &self.memory, defaults.memory)?,
+            rx: color("rx", &self.rx, defaults.rx)?,
+            tx: color("tx", &self.tx, defaults.tx)?,
+            read: color("read", &self.read, defaults.read)?,
+            write: color("write", &self.write, defaults.write)?,
+            selection: color("selection", &self.selection, defaults.selection)?,
+        })
+    }
+}
+
+fn ranged(
+    name: &str,
+    value: Option<u64>,
+    range: std::ops::RangeInclusive<u64>,
+) -> Result<Option<u64>> {
+    match value {
+        Some(value) if !range.contains(&value) => Err(eyre!(
+            "{name} must be between {} and {}, got {value}",
+            range.start(),
+            range.end()
+        )),
+        _ => Ok(value),
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    config_path(env::var_os("XDG_CONFIG_HOME"), env::var_os("HOME"))
+}
+
+/// `$XDG_CONFIG_HOME/<name>/config.toml`, with `$HOME/.config` standing in for an unset,
+/// empty or relative `XDG_CONFIG_HOME` as the XDG spec asks.
+fn config_path(xdg_config_home: Option<OsString>, home: Option<OsString>) -> Option<PathBuf> {
+    let base = xdg_config_home
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            home.filter(|home| !home.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(base.join(env!("CARGO_PKG_NAME")).join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(contents: &str) -> String {
+        format!("{:#}", Config::parse(contents).unwrap_err())
+    }
+
+    #[test]
+    fn default_config_matches_the_defaults() {
+        let parsed = Config::parse(DEFAULT_CONFIG).unwrap();
+        let defaults = Config::default();
+        assert_eq!(parsed.refresh_interval, defaults.refresh_interval);
+        assert_eq!(parsed.frame_interval, defaults.frame_interval);
+        assert_eq!(parsed.format, defaults.format);
+        assert_eq!(parsed.theme, defaults.theme);
+        assert!(parsed.layout.is_none());
+    }
+
+    #[test]
+    fn empty_file_uses_defaults() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.refresh_interval, Duration::from_millis(500));
+        assert_eq!(config.theme, Theme::default());
+    }
+
+    #[test]
+    fn values_override_defaults() {
+        let config = Config::parse(
+            "refresh_ms = 2000\nunits = \"si\"\nprecision = 0\n[colors]\ncpu = \"#ff8800\"\n\
+             [[layout.children]]\nwidget = \"processes\"",
+        )
+        .unwrap();
+        assert_eq!(config.refresh_interval, Duration::from_secs(2));
+        assert_eq!(config.format.units, UnitSystem::Si);
+        assert_eq!(config.format.precision, 0);
+        assert_eq!(config.theme.cpu, Color::Rgb(0xff, 0x88, 0x00));
+        assert!(config.layout.is_some());
+    }
+
+    #[test]
+    fn invalid_values_are_explained() {
+        assert!(error("refresh_ms = 5").contains("refresh_ms must be between 100 and 60000"));
+        assert!(error("precision = 9").contains("precision must be between 0 and 6"));
+        assert!(error("units = \"metric\"").contains("units: unknown unit system `metric`"));
+        assert!(error("[colors]\nlow = \"greenish\"").contains("colors.low: unknown color"));
+        assert!(error("refresh = 5").contains("unknown field `refresh`"));
+        assert!(error("[[layout.children]]").contains("layout.children[0]: needs either"));
+    }
+
+    #[test]
+    fn config_path_follows_xdg() {
+        let path = |xdg: Option<&Path>, home: Option<&str>| {
+            config_path(xdg.map(OsString::from), home.map(OsString::from))
+        };
+        let expected = |base: &Path| Some(base.join("ratatui-system-monitor").join("config.toml"));
+        // Absolute on every platform, unlike "/xdg" on Windows.
+        let xdg = std::env::temp_dir();
+        assert_eq!(path(Some(&xdg), Some("/home/me")), expected(&xdg));
+        let expected = |base: &str| expected(Path::new(base));
+        assert_eq!(
+            path(Some(Path::new("relative")), Some("/home/me")),
+            expected("/home/me/.config")
+        );
+        assert_eq!(path(None, Some("/home/me")), expected("/home/me/.config"));
+        assert_eq!(path(None, None), None);
+    }
+
+    #[test]
+    fn missing_explicit_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Config::load(Some(&dir.path().join("nope.toml"))).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols,
+    widgets::{Block, Borders, LineGauge, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+use crate::{format, history::HistoryStore, theme};
+
+/// Width of the per-core history sparkline.
+const TREND_WIDTH: u16 = 10;
+
+/// Smallest width a core column may shrink to before the grid stops adding columns.
+const MIN_COLUMN_WIDTH: u16 = 30;
+
+/// CPU usage of the whole machine and each logical core at one instant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CpuSnapshot {
+    pub global_usage: f32,
+    pub cores: Vec<CoreInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CoreInfo {
+    pub name: String,
+    pub usage: f32,
+    /// Current frequency in MHz.
+    pub frequency: u64,
+}
+
+impl CpuSnapshot {
+    pub(crate) fn sample(sys: &System) -> Self {
+        Self {
+            global_usage: sys.global_cpu_usage(),
+            cores: sys
+                .cpus()
+                .iter()
+                .map(|cpu| CoreInfo {
+                    name: cpu.name().to_string(),
+                    usage: cpu.cpu_usage(),
+                    frequency: cpu.frequency(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Per-core usage gauges, laid out in as many columns as the core count needs.
+#[derive(Debug, Default)]
+pub(crate) struct CpuPanel {
+    latest: Option<CpuSnapshot>,
+}
+
+impl CpuPanel {
+    /// Store the snapshot for display and append its values to `history`.
+    pub(crate) fn update(&mut self, snapshot: CpuSnapshot, history: &mut HistoryStore) {
+        let now = history.now();
+        history.record("cpu", now, snapshot.global_usage as f64);
+        for core in &snapshot.cores {
+            history.record(format!("cpu.{}", core.name), now, core.usage as f64);
+        }
+        self.latest = Some(snapshot);
+    }
+
+    pub(crate) fn render(&self, history: &HistoryStore, area: Rect, frame: &mut Frame) {
+        let Some(snapshot) = &self.latest else {
+            return;
+        };
+        let block = Block::new()
+            .title(format!(
+                "cpu info ({})",
+                format::percent(snapshot.global_usage as f64)
+            ))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(theme::current().border));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if snapshot.cores.is_empty() || inner.height == 0 {
+            return;
+        }
+
+        let rows = inner.height as usize;
+        let max_columns = (inner.width / MIN_COLUMN_WIDTH).max(1) as usize;
+        let columns = snapshot.cores.len().div_ceil(rows).clamp(1, max_columns);
+        let per_column = snapshot.cores.len().div_ceil(columns);
+        let column_areas =
+            Layout::horizontal(vec![Constraint::Ratio(1, columns as u32); columns]).split(inner);
+
+        for (column, area) in column_areas.iter().enumerate() {
+            let start = column * per_column;
+            let end = (start + per_column).min(snapshot.cores.len());
+            for (row, index) in (start..end).enumerate().take(rows) {
+                let line = Rect {
+                    y: area.y + row as u16,
+                    height: 1,
+                    ..*area
+                };
+                render_core(&snapshot.cores[index], history, line, frame);
+            }
+        }
+    }
+}
+
+fn render_core(core: &CoreInfo, history: &HistoryStore, area: Rect, frame: &mut Frame) {
+    let [gauge_area, frequency_area, trend_area] = Layout::horizontal([
+        Constraint::Min(10),
+        Constraint::Length(9),
+        Constraint::Length(TREND_WIDTH),
+    ])
+    .areas(area);
+    let gauge = LineGauge::default()
+        .label(format!(
+            "{:<6}{:>6}",
+            core.name,
+            format::percent(core.usage.into())
+        ))
+        .ratio((core.usage as f64 / 100.0).clamp(0.0, 1.0))
+        .filled_style(Style::default().fg(usage_color(core.usage)))
+        .unfilled_style(Style::default().fg(Color::DarkGray))
+        .line_set(symbols::line::THICK);
+    frame.render_widget(gauge, gauge_area);
+
+    let frequency = Paragraph::new(format!(" {:>4}MHz", core.frequency));
+    frame.render_widget(frequency, frequency_area);
+    let trend = history
+        .sparkline(&format!("cpu.{}", core.name), 100.0, TREND_WIDTH)
+        .style(Style::default().fg(theme::current().cpu));
+    frame.render_widget(trend, trend_area);
+}
+
+/// The theme's low color under 50%, medium under 80%, high above.
+pub(crate) fn usage_color(usage: f32) -> Color {
+    let theme = theme::current();
+    if usage < 50.0 {
+        theme.low
+    } else if usage < 80.0 {
+        theme.medium
+    } else {
+        theme.high
+    }
+}
@@ -0,0 +1,425 @@
+//! Overview layouts as trees of splits and widgets.
+//!
+//! Besides the built-in layouts, one can be given in TOML, either as the `[layout]` table
+//! of the config file or as a file of its own passed with `--layout`. Each node is either
+//! a `widget` or a split into `children`, and may give the `size` it takes in its parent.
+//! Splits alternate between rows and columns unless `direction` says otherwise. A
+//! CPU-heavy layout file might look like:
+//!
+//! ```toml
+//! [[children]]
+//! size = "40%"
+//!   [[children.children]]
+//!   widget = "cpu"
+//!   [[children.children]]
+//!   widget = "cpu-history"
+//!   size = "2/3"
+//!
+//! [[children]]
+//! widget = "processes"
+//! ```
+
+use std::{fs, path::Path};
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::Deserialize;
+
+use crate::layout::LayoutMode;
+
+/// A panel that can be placed in the overview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Widget {
+    Memory,
+    Cpu,
+    /// Chart of total CPU usage over the history window.
+    CpuHistory,
+    /// Sparkline of used memory over the history window.
+    MemoryHistory,
+    Network,
+    Disks,
+    DiskIo,
+    Sensors,
+    Processes,
+}
+
+/// One node of a layout as written in the config file: either a `widget` or a split
+/// into `children`, optionally with the `size` it takes inside its parent.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct LayoutSpec {
+    size: Option<SizeSpec>,
+    direction: Option<SplitDirection>,
+    widget: Option<Widget>,
+    #[serde(default)]
+    children: Vec<LayoutSpec>,
+}
+
+/// A size is either a bare number of cells or a string such as `"30%"`, `"1/3"`,
+/// `"min 10"`, `"max 40"` or `"fill 2"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SizeSpec {
+    Length(u16),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SplitDirection {
+    /// Children stacked top to bottom.
+    Rows,
+    /// Children side by side.
+    Columns,
+}
+
+impl SizeSpec {
+    fn constraint(&self) -> Result<Constraint, String> {
+        let text = match self {
+            SizeSpec::Length(cells) => return Ok(Constraint::Length(*cells)),
+            SizeSpec::Text(text) => text.trim(),
+        };
+        let number = |value: &str| {
+            value
+                .trim()
+                .parse::<u16>()
+                .map_err(|_| format!("invalid size `{text}`"))
+        };
+        if let Some(percent) = text.strip_suffix('%') {
+            let percent = number(percent)?;
+            if percent > 100 {
+                return Err(format!("size `{text}` is more than 100%"));
+            }
+            Ok(Constraint::Percentage(percent))
+        } else if let Some((numerator, denominator)) = text.split_once('/') {
+            let (numerator, denominator) = (number(numerator)?, number(denominator)?);
+            if denominator == 0 {
+                return Err(format!("size `{text}` divides by zero"));
+            }
+            Ok(Constraint::Ratio(numerator.into(), denominator.into()))
+        } else if let Some(cells) = text.strip_prefix("min") {
+            Ok(Constraint::Min(number(cells)?))
+        } else if let Some(cells) = text.strip_prefix("max") {
+            Ok(Constraint::Max(number(cells)?))
+        } else if let Some(weight) = text.strip_prefix("fill") {
+            Ok(Constraint::Fill(if weight.trim().is_empty() {
+                1
+            } else {
+                number(weight)?
+            }))
+        } else {
+            Ok(Constraint::Length(number(text)?))
+        }
+    }
+}
+
+/// A layout tree with its ratatui [`Layout`]s built, ready to be split every frame.
+#[derive(Debug, Clone)]
+pub(crate) enum Dashboard {
+    Widget(Widget),
+    Split {
+        layout: Layout,
+        children: Vec<Dashboard>,
+    },
+}
+
+impl Dashboard {
+    /// Read and validate a layout file.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("could not read layout file {}", path.display()))?;
+        Self::parse(&contents).wrap_err_with(|| format!("invalid layout in {}", path.display()))
+    }
+
+    pub(crate) fn parse(contents: &str) -> Result<Self> {
+        let spec: LayoutSpec = toml::from_str(contents)?;
+        Self::from_spec(&spec)
+    }
+
+    pub(crate) fn from_spec(spec: &LayoutSpec) -> Result<Self> {
+        if spec.size.is_some() {
+            return Err(eyre!("layout: the outermost node cannot have a `size`"));
+        }
+        build(spec, Direction::Vertical, "layout")
+    }
+
+    /// Every widget in the tree with the part of `area` it is drawn in.
+    pub(crate) fn areas(&self, area: Rect) -> Vec<(Widget, Rect)> {
+        let mut areas = Vec::new();
+        self.collect_areas(area, &mut areas);
+        areas
+    }
+
+    fn collect_areas(&self, area: Rect, areas: &mut Vec<(Widget, Rect)>) {
+        match self {
+            Dashboard::Widget(widget) => areas.push((*widget, area)),
+            Dashboard::Split { layout, children } => {
+                for (child, child_area) in children.iter().zip(layout.split(area).iter()) {
+                    child.collect_areas(*child_area, areas);
+                }
+            }
+        }
+    }
+}
+
+/// Turn `spec` into a [`Dashboard`]. Splits without an explicit direction run across
+/// their parent's, so nesting alternates between rows and columns. `path` names the node
+/// in error messages.
+fn build(spec: &LayoutSpec, default_direction: Direction, path: &str) -> Result<Dashboard> {
+    match (spec.widget, spec.children.is_empty()) {
+        (Some(_), false) => Err(eyre!("{path}: has both a `widget` and `children`")),
+        (None, true) => Err(eyre!("{path}: needs either a `widget` or `children`")),
+        (Some(widget), true) => {
+            if spec.direction.is_some() {
+                return Err(eyre!("{path}: a `widget` cannot have a `direction`"));
+            }
+            Ok(Dashboard::Widget(widget))
+        }
+        (None, false) => {
+            let direction = match spec.direction {
+                Some(SplitDirection::Rows) => Direction::Vertical,
+                Some(SplitDirection::Columns) => Direction::Horizontal,
+                None => default_direction,
+            };
+            let across = match direction {
+                Direction::Vertical => Direction::Horizontal,
+                Direction::Horizontal => Direction::Vertical,
+            };
+            let mut constraints = Vec::with_capacity(spec.children.len());
+            let mut children = Vec::with_capacity(spec.children.len());
+            for (index, child) in spec.children.iter().enumerate() {
+                let child_path = format!("{path}.children[{index}]");
+                let constraint = match &child.size {
+                    Some(size) => size
+                        .constraint()
+                        .map_err(|err| eyre!("{child_path}: {err}"))?,
+                    None => Constraint::Fill(1),
+                };
+                constraints.push(constraint);
+                children.push(build(child, across, &child_path)?);
+            }
+            Ok(Dashboard::Split {
+                layout: Layout::new(direction, constraints),
+                children,
+            })
+        }
+    }
+}
+
+fn split(direction: Direction, children: Vec<(Constraint, Dashboard)>) -> Dashboard {
+    let (constraints, children): (Vec<_>, Vec<_>) = children.into_iter().unzip();
+    Dashboard::Split {
+        layout: Layout::new(direction, constraints),
+        children,
+    }
+}
+
+fn rows(children: Vec<(Constraint, Dashboard)>) -> Dashboard {
+    split(Direction::Vertical, children)
+}
+
+fn columns(children: Vec<(Constraint, Dashboard)>) -> Dashboard {
+    split(Direction::Horizontal, children)
+}
+
+/// The overview layouts: the built-in one for each terminal size, unless the user
+/// supplied their own, which is then used at every size.
+#[derive(Debug, Clone)]
+pub(crate) struct Dashboards {
+    compact: Dashboard,
+    standard: Dashboard,
+    wide: Dashboard,
+    custom: Option<Dashboard>,
+}
+
+impl Dashboards {
+    pub(crate) fn new(custom: Option<Dashboard>) -> Self {
+        use Constraint::{Length, Min, Percentage, Ratio};
+        use Dashboard::Widget as W;
+        let storage = || {
+            rows(vec![
+                (Percentage(50), W(Widget::Disks)),
+                (Percentage(50), W(Widget::DiskIo)),
+            ])
+        };
+        // Memory, CPU and network stacked in a single column above the processes.
+        let compact = rows(vec![
+            (Length(8), W(Widget::Memory)),
+            (Percentage(20), W(Widget::Cpu)),
+            (Percentage(20), W(Widget::Network)),
+            (Min(0), W(Widget::Processes)),
+        ]);
+        let standard = rows(vec![
+            (
+                Percentage(25),
+                columns(vec![
+                    (Percentage(50), W(Widget::Memory)),
+                    (Percentage(50), W(Widget::Cpu)),
+                ]),
+            ),
+            (
+                Percentage(20),
+                columns(vec![
+                    (Percentage(45), W(Widget::CpuHistory)),
+                    (Percentage(20), W(Widget::MemoryHistory)),
+                    (Percentage(35), W(Widget::Sensors)),
+                ]),
+            ),
+            (
+                Percentage(20),
+                columns(vec![
+                    (Percentage(55), W(Widget::Network)),
+                    (Percentage(45), storage()),
+                ]),
+            ),
+            (Min(0), W(Widget::Processes)),
+        ]);
+        // Two rows of three panels above the processes, giving network the most room.
+        let wide = rows(vec![
+            (
+                Percentage(25),
+                columns(vec![
+                    (Ratio(1, 3), W(Widget::Memory)),
+                    (Ratio(1, 3), W(Widget::Cpu)),
+                    (
+                        Ratio(1, 3),
+                        columns(vec![
+                            (Percentage(70), W(Widget::CpuHistory)),
+                            (Percentage(30), W(Widget::MemoryHistory)),
+                        ]),
+                    ),
+                ]),
+            ),
+            (
+                Percentage(25),
+                columns(vec![
+                    (Percentage(40), W(Widget::Network)),
+                    (Percentage(35), storage()),
+                    (Percentage(25), W(Widget::Sensors)),
+                ]),
+            ),
+            (Min(0), W(Widget::Processes)),
+        ]);
+        Self {
+            compact,
+            standard,
+            wide,
+            custom,
+        }
+    }
+
+    pub(crate) fn for_mode(&self, mode: LayoutMode) -> &Dashboard {
+        if let Some(custom) = &self.custom {
+            return custom;
+        }
+        match mode {
+            LayoutMode::Compact => &self.compact,
+            LayoutMode::Wide => &self.wide,
+            LayoutMode::Standard | LayoutMode::TooSmall => &self.standard,
+        }
+    }
+}
+
+impl Default for Dashboards {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(contents: &str) -> String {
+        format!("{:#}", Dashboard::parse(contents).unwrap_err())
+    }
+
+    #[test]
+    fn nested_splits_alternate_direction() {
+        let dashboard = Dashboard::parse(
+            r#"
+            [[children]]
+            size = "25%"
+            [[children.children]]
+            widget = "cpu"
+            size = "1/4"
+            [[children.children]]
+            widget = "cpu-history"
+
+            [[children]]
+            widget = "processes"
+            "#,
+        )
+        .unwrap();
+
+        let areas = dashboard.areas(Rect::new(0, 0, 100, 40));
+
+        assert_eq!(
+            areas,
+            vec![
+                (Widget::Cpu, Rect::new(0, 0, 25, 10)),
+                (Widget::CpuHistory, Rect::new(25, 0, 75, 10)),
+                (Widget::Processes, Rect::new(0, 10, 100, 30)),
+            ]
+        );
+    }
+
+    #[test]
+    fn explicit_direction_overrides_the_default() {
+        let dashboard = Dashboard::parse(
+            r#"
+            direction = "columns"
+            [[children]]
+            widget = "network"
+            size = 30
+            [[children]]
+            widget = "sensors"
+            "#,
+        )
+        .unwrap();
+
+        let areas = dashboard.areas(Rect::new(0, 0, 100, 20));
+
+        assert_eq!(
+            areas,
+            vec![
+                (Widget::Network, Rect::new(0, 0, 30, 20)),
+                (Widget::Sensors, Rect::new(30, 0, 70, 20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn sizes_parse_into_constraints() {
+        let size = |text: &str| SizeSpec::Text(text.to_string()).constraint();
+        assert_eq!(size("30%"), Ok(Constraint::Percentage(30)));
+        assert_eq!(size("1/3"), Ok(Constraint::Ratio(1, 3)));
+        assert_eq!(size("min 10"), Ok(Constraint::Min(10)));
+        assert_eq!(size("max 40"), Ok(Constraint::Max(40)));
+        assert_eq!(size("fill"), Ok(Constraint::Fill(1)));
+        assert_eq!(size("fill 3"), Ok(Constraint::Fill(3)));
+        assert_eq!(size("12"), Ok(Constraint::Length(12)));
+        assert!(size("120%").is_err());
+        assert!(size("1/0").is_err());
+        assert!(size("big").is_err());
+    }
+
+    #[test]
+    fn invalid_nodes_name_their_position() {
+        assert!(
+            error("[[children]]\nwidget = \"cpu\"\nsize = \"lots\"")
+                .contains("layout.children[0]: invalid size `lots`")
+        );
+        assert!(
+            error("[[children]]\nwidget = \"cpu\"\n[[children.children]]\nwidget = \"memory\"")
+                .contains("layout.children[0]: has both")
+        );
+        assert!(error("widget = \"cpu\"\nsize = \"10%\"").contains("cannot have a `size`"));
+        assert!(error("direction = \"rows\"").contains("needs either"));
+        assert!(error("widget = \"gpu\"").contains("unknown variant"));
+    }
+}
@@ -0,0 +1,136 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Gauge, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+
+use crate::{cpu::usage_color, format};
+
+/// Capacity and mount details for one mounted filesystem.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DiskInfo {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total: u64,
+    pub available: u64,
+    pub removable: bool,
+    pub read_only: bool,
+}
+
+impl DiskInfo {
+    /// Describe every disk in `disks`, sorted by mount point.
+    pub(crate) fn snapshot(disks: &Disks) -> Vec<DiskInfo> {
+        let mut list: Vec<DiskInfo> = disks
+            .list()
+            .iter()
+            .map(|disk| DiskInfo {
+                name: disk.name().to_string_lossy().into_owned(),
+                mount_point: disk.mount_point().to_string_lossy().into_owned(),
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                total: disk.total_space(),
+                available: disk.available_space(),
+                removable: disk.is_removable(),
+                read_only: disk.is_read_only(),
+            })
+            .collect();
+        list.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        list
+    }
+
+    pub(crate) fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    /// Used space as a percentage of the total, zero for empty filesystems.
+    pub(crate) fn used_percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used() as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// One usage gauge per mounted filesystem with its mount details.
+#[derive(Debug, Default)]
+pub(crate) struct DiskPanel {
+    latest: Vec<DiskInfo>,
+}
+
+impl DiskPanel {
+    pub(crate) fn update(&mut self, disks: Vec<DiskInfo>) {
+        self.latest = disks;
+    }
+
+    pub(crate) fn render(&self, area: Rect, frame: &mut Frame) {
+        let block = Block::new().title("disks").borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let header = Rect {
+            height: 1.min(inner.height),
+            ..inner
+        };
+        frame.render_widget(
+            Paragraph::new(disk_line("MOUNT", "DEVICE", "FS", "AVAIL / TOTAL", "FLAGS"))
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+            header,
+        );
+        for (row, disk) in self.latest.iter().enumerate() {
+            let y = inner.y + 1 + row as u16;
+            if y >= inner.bottom() {
+                break;
+            }
+            let line = Rect {
+                y,
+                height: 1,
+                ..inner
+            };
+            let [text_area, gauge_area] =
+                Layout::horizontal([Constraint::Length(TEXT_WIDTH), Constraint::Min(0)])
+                    .areas(line);
+            let flags = match (disk.removable, disk.read_only) {
+                (true, true) => "rm,ro",
+                (true, false) => "rm",
+                (false, true) => "ro",
+                (false, false) => "",
+            };
+            let usage = format!(
+                "{} / {}",
+                format::bytes(disk.available),
+                format::bytes(disk.total)
+            );
+            frame.render_widget(
+                Paragraph::new(disk_line(
+                    &disk.mount_point,
+                    &disk.name,
+                    &disk.file_system,
+                    &usage,
+                    flags,
+                )),
+                text_area,
+            );
+            let percent = disk.used_percent();
+            let gauge = Gauge::default()
+                .label(format::percent(percent))
+                .ratio((percent / 100.0).clamp(0.0, 1.0))
+                .gauge_style(
+                    Style::default()
+                        .fg(usage_color(percent as f32))
+                        .bg(Color::Black),
+                );
+            frame.render_widget(gauge, gauge_area);
+        }
+    }
+}
+
+/// Width of the text columns drawn before each gauge.
+const TEXT_WIDTH: u16 = 16 + 1 + 12 + 1 + 7 + 1 + 23 + 1 + 6;
+
+fn disk_line(mount: &str, device: &str, fs: &str, space: &str, flags: &str) -> String {
+    format!("{mount:<16.16} {device:<12.12} {fs:<7.7} {space:<23.23} {flags:<6}")
+}
@@ -0,0 +1,316 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Row, Table},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    format,
+    history::{HistoryStore, TREND_PAIR_WIDTH},
+    theme,
+};
+
+/// /proc/diskstats always counts in 512-byte sectors, whatever the device's block size.
+const SECTOR_SIZE: u64 = 512;
+
+/// Activity of one block device over the last sample interval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DeviceIo {
+    pub name: String,
+    pub read_rate: f64,
+    pub write_rate: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    /// Average number of requests in flight over the interval.
+    pub queue_depth: f64,
+    /// Percentage of the interval the device was busy.
+    pub utilization: f64,
+}
+
+/// The cumulative counters of one /proc/diskstats line that the rates are derived from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RawStats {
+    reads: u64,
+    sectors_read: u64,
+    writes: u64,
+    sectors_written: u64,
+    io_ms: u64,
+    weighted_io_ms: u64,
+}
+
+impl RawStats {
+    /// Parse one line of /proc/diskstats into the device name and its counters.
+    fn parse(line: &str) -> Option<(&str, RawStats)> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let number = |index: usize| fields.get(index)?.parse::<u64>().ok();
+        Some((
+            fields.get(2)?,
+            RawStats {
+                reads: number(3)?,
+                sectors_read: number(5)?,
+                writes: number(7)?,
+                sectors_written: number(9)?,
+                io_ms: number(12)?,
+                weighted_io_ms: number(13)?,
+            },
+        ))
+    }
+}
+
+/// Turns successive reads of /proc/diskstats into per-device rates.
+#[derive(Debug)]
+pub(crate) struct DiskStatsSampler {
+    previous: HashMap<String, RawStats>,
+    last_sample: Instant,
+}
+
+impl DiskStatsSampler {
+    pub(crate) fn new() -> Self {
+        let mut sampler = Self {
+            previous: HashMap::new(),
+            last_sample: Instant::now(),
+        };
+        // Prime the counters so the first real sample has something to diff against.
+        sampler.sample();
+        sampler
+    }
+
+    /// Read /proc/diskstats and return rates since the previous call. `None` where the
+    /// file does not exist, i.e. on anything but Linux.
+    pub(crate) fn sample(&mut self) -> Option<Vec<DeviceIo>> {
+        let contents = std::fs::read_to_string("/proc/diskstats").ok()?;
+        let elapsed = self.last_sample.elapsed();
+        self.last_sample = Instant::now();
+        let current: HashMap<String, RawStats> = contents
+            .lines()
+            .filter_map(RawStats::parse)
+            .filter(|(name, _)| is_whole_device(Path::new("/sys/block"), name))
+            .map(|(name, stats)| (name.to_string(), stats))
+            .collect();
+
+        let mut devices: Vec<DeviceIo> = current
+            .iter()
+            .filter(|(_, stats)| stats.reads + stats.writes > 0)
+            .map(|(name, stats)| {
+                let previous = self.previous.get(name).copied().unwrap_or(*stats);
+                device_io(name, &previous, stats, elapsed)
+            })
+            .collect();
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        self.previous = current;
+        Some(devices)
+    }
+}
+
+fn device_io(name: &str, previous: &RawStats, current: &RawStats, elapsed: Duration) -> DeviceIo {
+    let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+    let millis = seconds * 1000.0;
+    // Counters reset when a device is re-attached; treat that as no activity.
+    let delta = |now: u64, before: u64| now.saturating_sub(before) as f64;
+    DeviceIo {
+        name: name.to_string(),
+        read_rate: delta(current.sectors_read, previous.sectors_read) * SECTOR_SIZE as f64
+            / seconds,
+        write_rate: delta(current.sectors_written, previous.sectors_written) * SECTOR_SIZE as f64
+            / seconds,
+        read_iops: delta(current.reads, previous.reads) / seconds,
+        write_iops: delta(current.writes, previous.writes) / seconds,
+        queue_depth: delta(current.weighted_io_ms, previous.weighted_io_ms) / millis,
+        utilization: (delta(current.io_ms, previous.io_ms) / millis * 100.0).min(100.0),
+    }
+}
+
+/// Skip partitions: only whole devices have an entry under `sys_block`, normally
+/// /sys/block. Without that directory every device is kept.
+fn is_whole_device(sys_block: &Path, name: &str) -> bool {
+    !sys_block.is_dir() || sys_block.join(name).exists()
+}
+
+/// Per-device throughput, IOPS, queue depth and utilization with read/write sparklines.
+#[derive(Debug, Default)]
+pub(crate) struct DiskIoPanel {
+    latest: Vec<DeviceIo>,
+}
+
+impl DiskIoPanel {
+    /// Store the snapshot for display and append its rates to `history`.
+    pub(crate) fn update(&mut self, devices: Vec<DeviceIo>, history: &mut HistoryStore) {
+        let now = history.now();
+        for device in &devices {
+            history.record(format!("io.{}.read", device.name), now, device.read_rate);
+            history.record(format!("io.{}.write", device.name), now, device.write_rate);
+        }
+        self.latest = devices;
+    }
+
+    pub(crate) fn render(&self, history: &HistoryStore, area: Rect, frame: &mut Frame) {
+        let block = Block::new().title("disk I/O").borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [table_area, trend_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(TREND_PAIR_WIDTH)])
+                .areas(inner);
+        let header = Row::new([
+            "DEVICE", "READ/s", "WRITE/s", "R IOPS", "W IOPS", "QUEUE", "UTIL",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.latest.iter().map(|device| {
+            Row::new([
+                device.name.clone(),
+                format::rate(device.read_rate),
+                format::rate(device.write_rate),
+                format!("{:.0}", device.read_iops),
+                format!("{:.0}", device.write_iops),
+                format!("{:.2}", device.queue_depth),
+                format::percent(device.utilization),
+            ])
+        });
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(7),
+        ];
+        frame.render_widget(Table::new(rows, widths).header(header), table_area);
+
+        let series: Vec<(String, String)> = self
+            .latest
+            .iter()
+            .map(|device| {
+                (
+                    format!("io.{}.read", device.name),
+                    format!("io.{}.write", device.name),
+                )
+            })
+            .collect();
+        history.render_trend_pairs(
+            ("read", theme::current().read),
+            ("write", theme::current().write),
+            &series,
+            trend_area,
+            frame,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDA: &str =
+        "   8       0 sda 1200 30 48000 900 600 20 16000 1500 0 2000 2400 0 0 0 0 0 0";
+
+    fn stats(reads: u64, sectors_read: u64, writes: u64, sectors_written: u64) -> RawStats {
+        RawStats {
+            reads,
+            sectors_read,
+            writes,
+            sectors_written,
+            io_ms: 0,
+            weighted_io_ms: 0,
+        }
+    }
+
+    #[test]
+    fn parses_a_diskstats_line() {
+        assert_eq!(
+            RawStats::parse(SDA),
+            Some((
+                "sda",
+                RawStats {
+                    reads: 1200,
+                    sectors_read: 48000,
+                    writes: 600,
+                    sectors_written: 16000,
+                    io_ms: 2000,
+                    weighted_io_ms: 2400,
+                },
+            ))
+        );
+    }
+
+    #[test]
+    fn short_or_garbage_lines_are_skipped() {
+        assert_eq!(RawStats::parse(""), None);
+        assert_eq!(RawStats::parse("   8       0 sda 1200 30 48000"), None);
+        assert_eq!(
+            RawStats::parse("   8       0 sda 1200 30 lots 900 600 20 16000 1500 0 2000 2400"),
+            None
+        );
+    }
+
+    #[test]
+    fn rates_are_per_second_of_the_interval() {
+        let previous = RawStats {
+            io_ms: 1000,
+            weighted_io_ms: 1000,
+            ..stats(100, 1000, 50, 2000)
+        };
+        let current = RawStats {
+            io_ms: 1500,
+            weighted_io_ms: 4000,
+            ..stats(300, 5000, 150, 6000)
+        };
+        let io = device_io("sda", &previous, &current, Duration::from_secs(2));
+        assert_eq!(io.name, "sda");
+        assert_eq!(io.read_rate, 4000.0 * 512.0 / 2.0);
+        assert_eq!(io.write_rate, 4000.0 * 512.0 / 2.0);
+        assert_eq!(io.read_iops, 100.0);
+        assert_eq!(io.write_iops, 50.0);
+        assert_eq!(io.queue_depth, 1.5);
+        assert_eq!(io.utilization, 25.0);
+    }
+
+    #[test]
+    fn utilization_is_clamped_to_full() {
+        let current = RawStats {
+            io_ms: 1200,
+            ..RawStats::default()
+        };
+        let io = device_io(
+            "sda",
+            &RawStats::default(),
+            &current,
+            Duration::from_secs(1),
+        );
+        assert_eq!(io.utilization, 100.0);
+    }
+
+    #[test]
+    fn reset_counters_count_as_idle() {
+        let previous = RawStats {
+            io_ms: 5000,
+            weighted_io_ms: 5000,
+            ..stats(900, 9000, 900, 9000)
+        };
+        let current = stats(10, 100, 10, 100);
+        let io = device_io("sda", &previous, &current, Duration::from_secs(1));
+        assert_eq!(io.read_rate, 0.0);
+        assert_eq!(io.write_rate, 0.0);
+        assert_eq!(io.read_iops, 0.0);
+        assert_eq!(io.write_iops, 0.0);
+        assert_eq!(io.queue_depth, 0.0);
+        assert_eq!(io.utilization, 0.0);
+    }
+
+    #[test]
+    fn partitions_are_not_whole_devices() {
+        let sys_block = tempfile::tempdir().unwrap();
+        std::fs::create_dir(sys_block.path().join("sda")).unwrap();
+        assert!(is_whole_device(sys_block.path(), "sda"));
+        assert!(!is_whole_device(sys_block.path(), "sda1"));
+        assert!(is_whole_device(&sys_block.path().join("missing"), "sda1"));
+    }
+}
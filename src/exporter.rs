@@ -0,0 +1,478 @@
+//! The `exporter` subcommand: serve the sampled metrics over HTTP at `/metrics` in the
+//! OpenMetrics text format, for Prometheus to scrape.
+//!
+//! One thread keeps sampling and re-renders the page; each connection gets a thread that
+//! answers with the latest page, so a scrape never waits on a refresh or another client.
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use color_eyre::{Result, eyre::WrapErr};
+use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+
+use crate::{
+    cli::ExporterArgs,
+    host::HostInfo,
+    process::{self, ProcessFilter},
+    sampler::{Sample, Sampler},
+};
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// How long a client may take to send its request before the connection is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) fn run(args: &ExporterArgs, interval: Duration) -> Result<()> {
+    let listener = TcpListener::bind(args.listen)
+        .wrap_err_with(|| format!("could not listen on {}", args.listen))?;
+    eprintln!("serving metrics on http://{}/metrics", args.listen);
+    let page = Arc::new(Mutex::new(None));
+    let filter = args.processes.filter();
+    let top = args.top;
+    let latest = Arc::clone(&page);
+    thread::spawn(move || {
+        let mut sampler = Sampler::new();
+        loop {
+            thread::sleep(interval.max(MINIMUM_CPU_UPDATE_INTERVAL));
+            let sample = sampler.sample();
+            let metrics = render(&sampler.host(), &sample, &filter, top);
+            *latest.lock().unwrap_or_else(|err| err.into_inner()) = Some(metrics);
+        }
+    });
+    serve(&listener, &page);
+    Ok(())
+}
+
+/// Answer every connection on its own thread, so a client that is slow to send its
+/// request cannot hold up the scrapes behind it.
+fn serve(listener: &TcpListener, page: &Arc<Mutex<Option<String>>>) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let page = Arc::clone(page);
+        thread::spawn(move || {
+            let metrics = page.lock().unwrap_or_else(|err| err.into_inner()).clone();
+            // A client hanging up mid-request is its own problem, not the exporter's.
+            let _ = respond(stream, metrics.as_deref());
+        });
+    }
+}
+
+/// Read the request line and answer it: the metrics page for `GET /metrics`, 503 until
+/// the first sample exists, and 404 or 405 for anything else.
+fn respond(mut stream: TcpStream, metrics: Option<&str>) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut request_line = String::new();
+    let mut reader = BufReader::new(&stream);
+    reader.read_line(&mut request_line)?;
+    // Drain the headers so the client sees a clean close.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = target.split('?').next().unwrap_or("");
+    let (status, content_type, body) = match (method, path, metrics) {
+        ("GET" | "HEAD", "/metrics", Some(metrics)) => ("200 OK", CONTENT_TYPE, metrics),
+        ("GET" | "HEAD", "/metrics", None) => (
+            "503 Service Unavailable",
+            "text/plain",
+            "no sample taken yet\n",
+        ),
+        ("GET" | "HEAD", "/", _) => ("200 OK", "text/plain", "metrics are at /metrics\n"),
+        ("GET" | "HEAD", _, _) => ("404 Not Found", "text/plain", "not found\n"),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n",
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(body.as_bytes())?;
+    }
+    stream.flush()
+}
+
+/// Builds an OpenMetrics exposition one metric family at a time.
+#[derive(Default)]
+struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    /// Start a family. `name` is given without the `_total` suffix counters add.
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.text, "# TYPE sysmon_{name} {kind}");
+        let _ = writeln!(self.text, "# HELP sysmon_{name} {help}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let _ = write!(self.text, "sysmon_{name}");
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape(value)))
+                .collect();
+            let _ = write!(self.text, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.text, " {value}");
+    }
+
+    fn finish(mut self) -> String {
+        self.text.push_str("# EOF\n");
+        self.text
+    }
+}
+
+/// Label values escape backslashes, double quotes and line feeds.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The whole exposition for one sample. Processes are only included when `top` is above
+/// zero, as the busiest `top` of those `filter` keeps.
+fn render(host: &HostInfo, sample: &Sample, filter: &ProcessFilter, top: usize) -> String {
+    let mut out = Exposition::default();
+
+    out.family(
+        "cpu_usage_ratio",
+        "gauge",
+        "CPU usage across all cores, from 0 to 1.",
+    );
+    out.sample(
+        "cpu_usage_ratio",
+        &[],
+        f64::from(sample.cpu.global_usage) / 100.0,
+    );
+    out.family(
+        "cpu_core_usage_ratio",
+        "gauge",
+        "CPU usage of one logical core, from 0 to 1.",
+    );
+    for core in &sample.cpu.cores {
+        let labels = [("core", core.name.as_str())];
+        out.sample(
+            "cpu_core_usage_ratio",
+            &labels,
+            f64::from(core.usage) / 100.0,
+        );
+    }
+    out.family(
+        "cpu_core_frequency_hertz",
+        "gauge",
+        "Current frequency of one logical core.",
+    );
+    for core in &sample.cpu.cores {
+        let labels = [("core", core.name.as_str())];
+        let hertz = core.frequency as f64 * 1e6;
+        out.sample("cpu_core_frequency_hertz", &labels, hertz);
+    }
+    out.family("load_average", "gauge", "System load average.");
+    for (period, load) in ["1m", "5m", "15m"].iter().zip(host.load_average) {
+        out.sample("load_average", &[("period", period)], load);
+    }
+    out.family(
+        "boot_time_seconds",
+        "gauge",
+        "Time the machine booted, in seconds since the Unix epoch.",
+    );
+    out.sample("boot_time_seconds", &[], host.boot_time as f64);
+
+    let memory = &sample.memory;
+    for (name, help, bytes) in [
+        ("memory_total_bytes", "Physical memory.", memory.total),
+        ("memory_used_bytes", "Physical memory in use.", memory.used),
+        (
+            "memory_available_bytes",
+            "Physical memory available for new allocations.",
+            memory.available,
+        ),
+        ("swap_total_bytes", "Swap space.", memory.swap_total),
+        ("swap_used_bytes", "Swap space in use.", memory.swap_used),
+    ] {
+        out.family(name, "gauge", help);
+        out.sample(name, &[], bytes as f64);
+    }
+
+    out.family(
+        "filesystem_size_bytes",
+        "gauge",
+        "Size of a mounted filesystem.",
+    );
+    for disk in &sample.disks {
+        out.sample(
+            "filesystem_size_bytes",
+            &filesystem_labels(&disk.name, &disk.mount_point, &disk.file_system),
+            disk.total as f64,
+        );
+    }
+    out.family(
+        "filesystem_available_bytes",
+        "gauge",
+        "Space left on a mounted filesystem.",
+    );
+    for disk in &sample.disks {
+        out.sample(
+            "filesystem_available_bytes",
+            &filesystem_labels(&disk.name, &disk.mount_point, &disk.file_system),
+            disk.available as f64,
+        );
+    }
+    if let Some(devices) = &sample.disk_io {
+        out.family(
+            "disk_read_bytes_per_second",
+            "gauge",
+            "Bytes read from a block device per second over the last interval.",
+        );
+        for device in devices {
+            let labels = [("device", device.name.as_str())];
+            out.sample("disk_read_bytes_per_second", &labels, device.read_rate);
+        }
+        out.family(
+            "disk_written_bytes_per_second",
+            "gauge",
+            "Bytes written to a block device per second over the last interval.",
+        );
+        for device in devices {
+            let labels = [("device", device.name.as_str())];
+            out.sample("disk_written_bytes_per_second", &labels, device.write_rate);
+        }
+        out.family(
+            "disk_utilization_ratio",
+            "gauge",
+            "Share of the last interval a block device was busy, from 0 to 1.",
+        );
+        for device in devices {
+            let labels = [("device", device.name.as_str())];
+            out.sample(
+                "disk_utilization_ratio",
+                &labels,
+                device.utilization / 100.0,
+            );
+        }
+    }
+
+    out.family(
+        "network_receive_bytes_per_second",
+        "gauge",
+        "Bytes received on an interface per second over the last interval.",
+    );
+    for interface in &sample.networks {
+        let labels = [("interface", interface.name.as_str())];
+        out.sample(
+            "network_receive_bytes_per_second",
+            &labels,
+            interface.rx_rate,
+        );
+    }
+    out.family(
+        "network_transmit_bytes_per_second",
+        "gauge",
+        "Bytes sent on an interface per second over the last interval.",
+    );
+    for interface in &sample.networks {
+        let labels = [("interface", interface.name.as_str())];
+        out.sample(
+            "network_transmit_bytes_per_second",
+            &labels,
+            interface.tx_rate,
+        );
+    }
+    out.family(
+        "network_errors",
+        "counter",
+        "Receive and transmit errors on an interface since it came up.",
+    );
+    for interface in &sample.networks {
+        let labels = [("interface", interface.name.as_str())];
+        out.sample("network_errors_total", &labels, interface.errors as f64);
+    }
+
+    out.family(
+        "temperature_celsius",
+        "gauge",
+        "Temperature reported by a hardware sensor.",
+    );
+    for sensor in &sample.components {
+        if let Some(temperature) = sensor.temperature {
+            let labels = [("sensor", sensor.label.as_str())];
+            out.sample("temperature_celsius", &labels, f64::from(temperature));
+        }
+    }
+
+    if top > 0 {
+        let mut processes = sample.processes.clone();
+        process::keep_busiest(&mut processes, filter, Some(top));
+        out.family(
+            "process_cpu_usage_ratio",
+            "gauge",
+            "CPU usage of one of the busiest processes, where 1 is one full core.",
+        );
+        for process in &processes {
+            let pid = process.pid.to_string();
+            let labels = [("pid", pid.as_str()), ("name", process.name.as_str())];
+            let usage = f64::from(process.cpu_usage) / 100.0;
+            out.sample("process_cpu_usage_ratio", &labels, usage);
+        }
+        out.family(
+            "process_resident_memory_bytes",
+            "gauge",
+            "Resident memory of one of the busiest processes.",
+        );
+        for process in &processes {
+            let pid = process.pid.to_string();
+            let labels = [("pid", pid.as_str()), ("name", process.name.as_str())];
+            out.sample(
+                "process_resident_memory_bytes",
+                &labels,
+                process.memory as f64,
+            );
+        }
+    }
+
+    out.finish()
+}
+
+fn filesystem_labels<'a>(
+    device: &'a str,
+    mount_point: &'a str,
+    file_system: &'a str,
+) -> [(&'static str, &'a str); 3] {
+    [
+        ("device", device),
+        ("mountpoint", mount_point),
+        ("fstype", file_system),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, net::Shutdown, time::Instant};
+
+    use super::*;
+    use crate::{cpu::CoreInfo, network::InterfaceInfo, process::ProcessInfo};
+
+    fn sample() -> Sample {
+        let mut sample = Sample::example();
+        sample.cpu.global_usage = 25.0;
+        sample.cpu.cores = vec![CoreInfo {
+            name: "cpu0".to_string(),
+            usage: 50.0,
+            frequency: 2100,
+        }];
+        sample.processes = vec![
+            ProcessInfo {
+                cpu_usage: 0.5,
+                ..ProcessInfo::example(1, "init")
+            },
+            ProcessInfo {
+                cpu_usage: 150.0,
+                ..ProcessInfo::example(7, "my \"app\"")
+            },
+        ];
+        sample.networks = vec![InterfaceInfo {
+            name: "eth0".to_string(),
+            rx_rate: 100.0,
+            tx_rate: 50.0,
+            rx_packet_rate: 0.0,
+            tx_packet_rate: 0.0,
+            errors: 3,
+            mac: String::new(),
+            addresses: Vec::new(),
+            loopback: false,
+            is_virtual: false,
+        }];
+        sample
+    }
+
+    fn host() -> HostInfo {
+        HostInfo {
+            boot_time: 1_700_000_000,
+            ..HostInfo::example()
+        }
+    }
+
+    #[test]
+    fn renders_families_and_samples() {
+        let text = render(&host(), &sample(), &ProcessFilter::default(), 0);
+        assert!(text.contains(
+            "# TYPE sysmon_cpu_usage_ratio gauge\n\
+             # HELP sysmon_cpu_usage_ratio CPU usage across all cores, from 0 to 1.\n\
+             sysmon_cpu_usage_ratio 0.25\n"
+        ));
+        assert!(text.contains("sysmon_cpu_core_usage_ratio{core=\"cpu0\"} 0.5\n"));
+        assert!(text.contains("sysmon_cpu_core_frequency_hertz{core=\"cpu0\"} 2100000000\n"));
+        assert!(text.contains("sysmon_load_average{period=\"5m\"} 0.25\n"));
+        assert!(text.contains("sysmon_memory_used_bytes 1024\n"));
+        assert!(text.contains("sysmon_network_receive_bytes_per_second{interface=\"eth0\"} 100\n"));
+        assert!(text.contains("# TYPE sysmon_network_errors counter\n"));
+        assert!(text.contains("sysmon_network_errors_total{interface=\"eth0\"} 3\n"));
+        assert!(!text.contains("process_"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn busiest_processes_are_optional_and_escaped() {
+        let text = render(&host(), &sample(), &ProcessFilter::default(), 1);
+        assert!(
+            text.contains(
+                "sysmon_process_cpu_usage_ratio{pid=\"7\",name=\"my \\\"app\\\"\"} 1.5\n"
+            )
+        );
+        assert!(!text.contains("pid=\"1\""));
+    }
+
+    #[test]
+    fn serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let get = |path: &str| {
+            let mut client = TcpStream::connect(address).unwrap();
+            write!(client, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let (server, _) = listener.accept().unwrap();
+            respond(server, Some("sysmon_up 1\n# EOF\n")).unwrap();
+            client.shutdown(Shutdown::Write).unwrap();
+            let mut response = String::new();
+            client.read_to_string(&mut response).unwrap();
+            response
+        };
+        let metrics = get("/metrics");
+        assert!(metrics.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(metrics.contains(CONTENT_TYPE));
+        assert!(metrics.ends_with("\r\n\r\nsysmon_up 1\n# EOF\n"));
+        assert!(get("/other").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[test]
+    fn a_silent_client_does_not_hold_up_others() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let page = Arc::new(Mutex::new(Some("sysmon_up 1\n# EOF\n".to_string())));
+        thread::spawn(move || serve(&listener, &page));
+        let _silent = TcpStream::connect(address).unwrap();
+        let started = Instant::now();
+        let mut client = TcpStream::connect(address).unwrap();
+        write!(client, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(started.elapsed() < REQUEST_TIMEOUT);
+    }
+}
@@ -0,0 +1,265 @@
+//! Human-readable rendering of bytes, rates, percentages, durations and timestamps.
+//!
+//! Every widget goes through the free functions here so the unit system and precision
+//! chosen at startup apply everywhere. [`init`] sets them once; before that (and in
+//! tests) [`Format::default`] is used.
+
+use std::{fmt, str::FromStr, sync::OnceLock, time::Duration};
+
+/// Whether byte counts scale by 1024 (KiB, MiB, ...) or 1000 (kB, MB, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum UnitSystem {
+    #[default]
+    Iec,
+    Si,
+}
+
+impl UnitSystem {
+    fn base(self) -> f64 {
+        match self {
+            UnitSystem::Iec => 1024.0,
+            UnitSystem::Si => 1000.0,
+        }
+    }
+
+    fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            UnitSystem::Iec => &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"],
+            UnitSystem::Si => &["B", "kB", "MB", "GB", "TB", "PB", "EB"],
+        }
+    }
+}
+
+impl FromStr for UnitSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "iec" => Ok(UnitSystem::Iec),
+            "si" => Ok(UnitSystem::Si),
+            other => Err(format!(
+                "unknown unit system `{other}`, expected `iec` or `si`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UnitSystem::Iec => "iec",
+            UnitSystem::Si => "si",
+        })
+    }
+}
+
+/// Unit system and number of decimal places used for scaled values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Format {
+    pub units: UnitSystem,
+    pub precision: usize,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            units: UnitSystem::Iec,
+            precision: 1,
+        }
+    }
+}
+
+static FORMAT: OnceLock<Format> = OnceLock::new();
+
+/// Set the process-wide format. Only the first call has any effect.
+pub(crate) fn init(format: Format) {
+    let _ = FORMAT.set(format);
+}
+
+fn current() -> Format {
+    FORMAT.get().copied().unwrap_or_default()
+}
+
+/// `bytes` scaled to the largest unit that keeps the value at or above one, e.g. `1.5 GiB`.
+pub(crate) fn bytes(bytes: u64) -> String {
+    current().bytes(bytes)
+}
+
+/// A throughput in bytes per second, e.g. `12.0 MiB/s`.
+pub(crate) fn rate(bytes_per_second: f64) -> String {
+    current().rate(bytes_per_second)
+}
+
+/// A percentage that is already in the 0-100 range, e.g. `42.5%`.
+pub(crate) fn percent(value: f64) -> String {
+    current().percent(value)
+}
+
+/// A duration as `[Nd ]HH:MM:SS`.
+pub(crate) fn duration(duration: Duration) -> String {
+    current().duration(duration)
+}
+
+/// Seconds since the Unix epoch as a UTC date and time, e.g. `2024-03-01 09:30:00 UTC`.
+pub(crate) fn timestamp(secs: u64) -> String {
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (hours, minutes, seconds) = (rest / 3600, rest % 3600 / 60, rest % 60);
+    // Days to a proleptic Gregorian date, counting 400-year eras from 0000-03-01 so leap
+    // days fall at the end of each year.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02} {hours:02}:{minutes:02}:{seconds:02} UTC")
+}
+
+impl Format {
+    pub(crate) fn bytes(&self, bytes: u64) -> String {
+        self.scaled(bytes as f64)
+    }
+
+    pub(crate) fn rate(&self, bytes_per_second: f64) -> String {
+        format!("{}/s", self.scaled(bytes_per_second.max(0.0)))
+    }
+
+    pub(crate) fn percent(&self, value: f64) -> String {
+        format!("{value:.prec$}%", prec = self.precision)
+    }
+
+    pub(crate) fn duration(&self, duration: Duration) -> String {
+        let secs = duration.as_secs();
+        let (days, rest) = (secs / 86_400, secs % 86_400);
+        let (hours, minutes, seconds) = (rest / 3600, rest % 3600 / 60, rest % 60);
+        if days > 0 {
+            format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+        } else {
+            format!("{hours:02}:{minutes:02}:{seconds:02}")
+        }
+    }
+
+    fn scaled(&self, value: f64) -> String {
+        let base = self.units.base();
+        let suffixes = self.units.suffixes();
+        let mut value = value;
+        let mut unit = 0;
+        while value >= base && unit < suffixes.len() - 1 {
+            value /= base;
+            unit += 1;
+        }
+        // Whole bytes are shown without decimals.
+        let precision = |unit: usize| if unit == 0 { 0 } else { self.precision };
+        // Rounding can carry into the next unit (1023.96 KiB at one decimal is "1024.0",
+        // 1023.6 B as a whole number is "1024").
+        let factor = 10f64.powi(precision(unit) as i32);
+        if (value * factor).round() / factor >= base && unit < suffixes.len() - 1 {
+            value /= base;
+            unit += 1;
+        }
+        format!("{value:.prec$} {}", suffixes[unit], prec = precision(unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IEC: Format = Format {
+        units: UnitSystem::Iec,
+        precision: 1,
+    };
+    const SI: Format = Format {
+        units: UnitSystem::Si,
+        precision: 1,
+    };
+
+    #[test]
+    fn bytes_below_one_unit_are_whole_numbers() {
+        assert_eq!(IEC.bytes(0), "0 B");
+        assert_eq!(IEC.bytes(1023), "1023 B");
+        assert_eq!(SI.bytes(999), "999 B");
+    }
+
+    #[test]
+    fn bytes_scale_by_unit_system() {
+        assert_eq!(IEC.bytes(1024), "1.0 KiB");
+        assert_eq!(SI.bytes(1000), "1.0 kB");
+        assert_eq!(IEC.bytes(1536 * 1024 * 1024), "1.5 GiB");
+        assert_eq!(SI.bytes(1_500_000_000), "1.5 GB");
+    }
+
+    #[test]
+    fn rounding_carries_into_the_next_unit() {
+        // 1023.96 KiB would print as "1024.0 KiB" without the carry.
+        assert_eq!(IEC.bytes(1_048_535), "1.0 MiB");
+        assert_eq!(SI.bytes(999_960), "1.0 MB");
+        // Just below the rounding boundary stays in the smaller unit.
+        assert_eq!(IEC.bytes(1_048_473), "1023.9 KiB");
+        assert_eq!(IEC.rate(1023.6), "1.0 KiB/s");
+        assert_eq!(IEC.rate(1023.4), "1023 B/s");
+    }
+
+    #[test]
+    fn precision_applies_to_scaled_values() {
+        let format = Format {
+            units: UnitSystem::Iec,
+            precision: 3,
+        };
+        assert_eq!(format.bytes(1024 + 512), "1.500 KiB");
+        assert_eq!(format.percent(12.34567), "12.346%");
+        let format = Format {
+            units: UnitSystem::Si,
+            precision: 0,
+        };
+        assert_eq!(format.bytes(1_499), "1 kB");
+        assert_eq!(format.bytes(999_600), "1 MB");
+    }
+
+    #[test]
+    fn timestamps_are_utc_dates() {
+        assert_eq!(timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(timestamp(951_782_400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(timestamp(1_709_285_400), "2024-03-01 09:30:00 UTC");
+        assert_eq!(timestamp(1_735_689_599), "2024-12-31 23:59:59 UTC");
+    }
+
+    #[test]
+    fn largest_unit_does_not_overflow_the_suffix_table() {
+        assert_eq!(IEC.bytes(u64::MAX), "16.0 EiB");
+    }
+
+    #[test]
+    fn rates_are_suffixed_and_never_negative() {
+        assert_eq!(IEC.rate(2048.0), "2.0 KiB/s");
+        assert_eq!(IEC.rate(-5.0), "0 B/s");
+    }
+
+    #[test]
+    fn percent_rounds_half_values() {
+        assert_eq!(IEC.percent(99.95), "100.0%");
+        assert_eq!(IEC.percent(0.04), "0.0%");
+    }
+
+    #[test]
+    fn durations_show_days_only_when_needed() {
+        assert_eq!(IEC.duration(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(IEC.duration(Duration::from_secs(3661)), "01:01:01");
+        assert_eq!(IEC.duration(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+
+    #[test]
+    fn unit_system_parses_case_insensitively() {
+        assert_eq!("SI".parse::<UnitSystem>(), Ok(UnitSystem::Si));
+        assert_eq!("iec".parse::<UnitSystem>(), Ok(UnitSystem::Iec));
+        assert!("metric".parse::<UnitSystem>().is_err());
+    }
+}
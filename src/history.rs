@@ -0,0 +1,352 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
+};
+
+/// Samples older than this are dropped, whatever window is on screen.
+pub(crate) const RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// How often [`HistoryStore::record`] looks for series that have stopped being updated.
+const PRUNE_EVERY: Duration = Duration::from_secs(60);
+
+/// Width of each sparkline drawn by [`HistoryStore::render_trend_pairs`].
+const TREND_WIDTH: u16 = 20;
+
+/// Width of the area [`HistoryStore::render_trend_pairs`] needs for two sparklines.
+pub(crate) const TREND_PAIR_WIDTH: u16 = TREND_WIDTH * 2 + 3;
+
+/// How far back the charts look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum HistoryWindow {
+    #[default]
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl HistoryWindow {
+    pub(crate) fn duration(self) -> Duration {
+        match self {
+            HistoryWindow::OneMinute => Duration::from_secs(60),
+            HistoryWindow::FiveMinutes => Duration::from_secs(5 * 60),
+            HistoryWindow::OneHour => Duration::from_secs(60 * 60),
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            HistoryWindow::OneMinute => "1m",
+            HistoryWindow::FiveMinutes => "5m",
+            HistoryWindow::OneHour => "1h",
+        }
+    }
+
+    pub(crate) fn next(self) -> Self {
+        match self {
+            HistoryWindow::OneMinute => HistoryWindow::FiveMinutes,
+            HistoryWindow::FiveMinutes => HistoryWindow::OneHour,
+            HistoryWindow::OneHour => HistoryWindow::OneMinute,
+        }
+    }
+}
+
+/// Time series for a single metric, bounded to [`RETENTION`].
+#[derive(Debug, Default)]
+pub(crate) struct History {
+    samples: VecDeque<(Instant, f64)>,
+}
+
+impl History {
+    pub(crate) fn push(&mut self, at: Instant, value: f64) {
+        while let Some((oldest, _)) = self.samples.front() {
+            if at.duration_since(*oldest) <= RETENTION {
+                break;
+            }
+            self.samples.pop_front();
+        }
+        self.samples.push_back((at, value));
+    }
+
+    fn newest(&self) -> Option<Instant> {
+        self.samples.back().map(|(at, _)| *at)
+    }
+
+    /// Samples inside `window` as `(seconds relative to now, value)`; x is never positive.
+    pub(crate) fn points(&self, now: Instant, window: Duration) -> Vec<(f64, f64)> {
+        self.samples
+            .iter()
+            .rev()
+            .map(|(at, value)| (-now.duration_since(*at).as_secs_f64(), *value))
+            .take_while(|(x, _)| -x <= window.as_secs_f64())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect()
+    }
+
+    /// Split `window` into `count` equal slots, oldest first, holding the largest sample in
+    /// each so short spikes survive downsampling. Empty slots are zero.
+    pub(crate) fn buckets(&self, now: Instant, window: Duration, count: usize) -> Vec<f64> {
+        let mut buckets = vec![0.0_f64; count];
+        if count == 0 {
+            return buckets;
+        }
+        let slot = window.as_secs_f64() / count as f64;
+        for (x, value) in self.points(now, window) {
+            let from_end = (-x / slot) as usize;
+            if let Some(bucket) = count
+                .checked_sub(from_end + 1)
+                .and_then(|i| buckets.get_mut(i))
+            {
+                *bucket = bucket.max(value);
+            }
+        }
+        buckets
+    }
+}
+
+/// Rolling history for every metric the app displays, keyed by metric name
+/// (e.g. `cpu`, `cpu.cpu0`, `memory.used`).
+#[derive(Debug, Default)]
+pub(crate) struct HistoryStore {
+    series: BTreeMap<String, History>,
+    pub window: HistoryWindow,
+    /// Stands in for the current time while a recording is replayed.
+    frozen_at: Option<Instant>,
+    last_pruned: Option<Instant>,
+}
+
+impl HistoryStore {
+    /// The time new samples are recorded at and charts end at.
+    pub(crate) fn now(&self) -> Instant {
+        self.frozen_at.unwrap_or_else(Instant::now)
+    }
+
+    /// Stop following the wall clock and treat `at` as the current time.
+    pub(crate) fn freeze(&mut self, at: Instant) {
+        self.frozen_at = Some(at);
+    }
+
+    /// Forget every sample, keeping the window.
+    pub(crate) fn clear(&mut self) {
+        self.series.clear();
+        self.last_pruned = None;
+    }
+
+    /// Append a sample to `name`. Every [`PRUNE_EVERY`], series with nothing newer than
+    /// [`RETENTION`] are dropped, such as those of network interfaces that went away.
+    pub(crate) fn record(&mut self, name: impl Into<String>, at: Instant, value: f64) {
+        self.series.entry(name.into()).or_default().push(at, value);
+        if self
+            .last_pruned
+            .is_none_or(|pruned| at.saturating_duration_since(pruned) >= PRUNE_EVERY)
+        {
+            self.last_pruned = Some(at);
+            self.series.retain(|_, history| {
+                history
+                    .newest()
+                    .is_some_and(|newest| at.saturating_duration_since(newest) <= RETENTION)
+            });
+        }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&History> {
+        self.series.get(name)
+    }
+
+    /// Largest value of `name` inside the current window, or zero if there is none.
+    pub(crate) fn window_max(&self, name: &str) -> f64 {
+        self.get(name)
+            .map(|history| history.points(self.now(), self.window.duration()))
+            .unwrap_or_default()
+            .iter()
+            .fold(0.0, |max, (_, value)| max.max(*value))
+    }
+
+    /// A one-line-per-cell sparkline of `name` over the current window, scaled to `max`.
+    pub(crate) fn sparkline(&self, name: &str, max: f64, width: u16) -> Sparkline<'static> {
+        let data: Vec<u64> = self
+            .get(name)
+            .map(|history| history.buckets(self.now(), self.window.duration(), width as usize))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|value| (value / max * 100.0).round() as u64)
+            .collect();
+        Sparkline::default().data(data).max(100)
+    }
+
+    /// Two auto-scaled sparklines per row, one row per `(left, right)` pair of series
+    /// names, under a header line naming the two columns. Meant to sit beside a table
+    /// so each row lines up with the table row for the same device.
+    pub(crate) fn render_trend_pairs(
+        &self,
+        (left_label, left_color): (&str, Color),
+        (right_label, right_color): (&str, Color),
+        series: &[(String, String)],
+        area: Rect,
+        frame: &mut Frame,
+    ) {
+        if area.height == 0 {
+            return;
+        }
+        let header = format!(
+            " {left_label:<width$}  {right_label}",
+            width = TREND_WIDTH as usize
+        );
+        frame.render_widget(Paragraph::new(header), Rect { height: 1, ..area });
+        for (row, (left, right)) in series.iter().enumerate() {
+            let y = area.y + 1 + row as u16;
+            if y >= area.bottom() {
+                break;
+            }
+            let line = Rect {
+                y,
+                height: 1,
+                ..area
+            };
+            let [_, left_area, _, right_area] = Layout::horizontal([
+                Constraint::Length(1),
+                Constraint::Length(TREND_WIDTH),
+                Constraint::Length(2),
+                Constraint::Length(TREND_WIDTH),
+            ])
+            .areas(line);
+            for (name, area, color) in [
+                (left, left_area, left_color),
+                (right, right_area, right_color),
+            ] {
+                let max = self.window_max(name).max(1.0);
+                let sparkline = self
+                    .sparkline(name, max, TREND_WIDTH)
+                    .style(Style::default().fg(color));
+                frame.render_widget(sparkline, area);
+            }
+        }
+    }
+
+    /// Line chart of the named series over the current window, with a time x axis.
+    pub(crate) fn render_chart(
+        &self,
+        title: &str,
+        series: &[(&str, &str, Color)],
+        y_max: f64,
+        y_unit: &str,
+        area: Rect,
+        frame: &mut Frame,
+    ) {
+        let now = self.now();
+        let window = self.window.duration();
+        let points: Vec<Vec<(f64, f64)>> = series
+            .iter()
+            .map(|(name, _, _)| {
+                self.get(name)
+                    .map(|history| history.points(now, window))
+                    .unwrap_or_default()
+            })
+            .collect();
+        let datasets = series
+            .iter()
+            .zip(&points)
+            .map(|((_, label, color), data)| {
+                Dataset::default()
+                    .name(*label)
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(*color))
+                    .data(data)
+            })
+            .collect();
+
+        let seconds = window.as_secs_f64();
+        let x_axis = Axis::default()
+            .style(Style::default().fg(Color::DarkGray))
+            .bounds([-seconds, 0.0])
+            .labels([
+                Span::raw(format!("-{}", self.window.label())),
+                Span::raw(format!("-{}", format_ago(window / 2))),
+                Span::raw("now"),
+            ]);
+        let y_axis = Axis::default()
+            .style(Style::default().fg(Color::DarkGray))
+            .bounds([0.0, y_max])
+            .labels([
+                Span::raw(format!("0{y_unit}")),
+                Span::raw(format!("{:.0}{y_unit}", y_max / 2.0)),
+                Span::raw(format!("{y_max:.0}{y_unit}")),
+            ]);
+        let chart = Chart::new(datasets)
+            .block(
+                Block::new()
+                    .title(format!("{title} (last {})", self.window.label()))
+                    .borders(Borders::ALL),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis);
+        frame.render_widget(chart, area);
+    }
+}
+
+fn format_ago(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m{s}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minute of samples ending at the returned instant: 9 exactly a minute old, then 1,
+    /// 4 and 2 at 55, 45 and 10 seconds old.
+    fn minute() -> (History, Instant) {
+        let start = Instant::now();
+        let mut history = History::default();
+        for (offset, value) in [(0, 9.0), (5, 1.0), (15, 4.0), (50, 2.0)] {
+            history.push(start + Duration::from_secs(offset), value);
+        }
+        (history, start + Duration::from_secs(60))
+    }
+
+    #[test]
+    fn points_are_relative_to_now_and_cut_to_the_window() {
+        let (history, now) = minute();
+        assert_eq!(
+            history.points(now, Duration::from_secs(60)),
+            [(-60.0, 9.0), (-55.0, 1.0), (-45.0, 4.0), (-10.0, 2.0)]
+        );
+        assert_eq!(history.points(now, Duration::from_secs(30)), [(-10.0, 2.0)]);
+    }
+
+    #[test]
+    fn buckets_keep_the_largest_sample_of_each_slot() {
+        let (history, now) = minute();
+        let window = Duration::from_secs(60);
+        // The sample a whole window old falls just outside the oldest slot.
+        assert_eq!(history.buckets(now, window, 3), [4.0, 0.0, 2.0]);
+        assert!(history.buckets(now, window, 0).is_empty());
+    }
+
+    #[test]
+    fn series_that_stop_updating_are_dropped() {
+        let start = Instant::now();
+        let mut history = HistoryStore::default();
+        history.record("net.veth0.rx", start, 1.0);
+        history.record("cpu", start, 1.0);
+        let later = start + RETENTION + PRUNE_EVERY;
+        history.record("cpu", later, 2.0);
+        assert!(history.get("net.veth0.rx").is_none());
+        assert_eq!(history.get("cpu").map(|cpu| cpu.samples.len()), Some(1));
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+use crate::{cpu::usage_color, format};
+
+/// How often the host header is re-sampled. None of it moves quickly; the uptime shown
+/// in between is extrapolated from the time of the last sample.
+pub(crate) const HOST_REFRESH: Duration = Duration::from_secs(5);
+
+/// Identity and load of the machine being monitored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HostInfo {
+    pub host_name: String,
+    pub os: String,
+    pub kernel: String,
+    /// Seconds since the Unix epoch at which the machine booted.
+    pub boot_time: u64,
+    pub uptime: Duration,
+    pub load_average: [f64; 3],
+    pub cpu_brand: String,
+    pub physical_cores: Option<usize>,
+    pub logical_cores: usize,
+    /// When the sample was taken; `None` for a sample replayed from a recording.
+    #[serde(skip)]
+    pub sampled_at: Option<Instant>,
+}
+
+impl HostInfo {
+    /// `sys` only needs its CPU list refreshed; everything else is read directly.
+    pub(crate) fn sample(sys: &System) -> Self {
+        let load = System::load_average();
+        let unknown = || "unknown".to_string();
+        Self {
+            host_name: System::host_name().unwrap_or_else(unknown),
+            os: System::long_os_version().unwrap_or_else(unknown),
+            kernel: System::kernel_version().unwrap_or_else(unknown),
+            boot_time: System::boot_time(),
+            uptime: Duration::from_secs(System::uptime()),
+            load_average: [load.one, load.five, load.fifteen],
+            cpu_brand: sys
+                .cpus()
+                .first()
+                .map(|cpu| cpu.brand().trim().to_string())
+                .filter(|brand| !brand.is_empty())
+                .unwrap_or_else(unknown),
+            physical_cores: System::physical_core_count(),
+            logical_cores: sys.cpus().len(),
+            sampled_at: Some(Instant::now()),
+        }
+    }
+
+    /// Time since boot, from the wall clock when the boot time is known and otherwise
+    /// from the sampled uptime plus the time since sampling. A replayed sample shows the
+    /// uptime it was recorded with.
+    pub(crate) fn current_uptime(&self) -> Duration {
+        let Some(sampled_at) = self.sampled_at else {
+            return self.uptime;
+        };
+        let since_boot = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|now| now.checked_sub(Duration::from_secs(self.boot_time)));
+        match since_boot {
+            Some(uptime) if self.boot_time > 0 => uptime,
+            _ => self.uptime + sampled_at.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl HostInfo {
+    /// A lightly loaded four-thread Linux box for tests to adjust.
+    pub(crate) fn example() -> Self {
+        Self {
+            host_name: "box".to_string(),
+            os: "Linux".to_string(),
+            kernel: "6.1".to_string(),
+            boot_time: 0,
+            uptime: Duration::from_secs(90),
+            load_average: [0.5, 0.25, 0.0],
+            cpu_brand: "cpu".to_string(),
+            physical_cores: Some(2),
+            logical_cores: 4,
+            sampled_at: Some(Instant::now()),
+        }
+    }
+}
+
+/// One-line header naming the machine, its OS and its load.
+#[derive(Debug, Default)]
+pub(crate) struct HostHeader {
+    latest: Option<HostInfo>,
+}
+
+impl HostHeader {
+    pub(crate) fn update(&mut self, host: HostInfo) {
+        self.latest = Some(host);
+    }
+
+    pub(crate) fn render(&self, area: Rect, frame: &mut Frame) {
+        let Some(host) = &self.latest else {
+            frame.render_widget(Paragraph::new("collecting host information…"), area);
+            return;
+        };
+        let separator = || Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+        let cores = match host.physical_cores {
+            Some(physical) => format!("{physical}C/{}T", host.logical_cores),
+            None => format!("{}T", host.logical_cores),
+        };
+        let mut spans = vec![
+            Span::styled(
+                host.host_name.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            separator(),
+            Span::raw(host.os.clone()),
+            separator(),
+            Span::raw(format!("kernel {}", host.kernel)),
+            separator(),
+            Span::raw(format!("up {}", format::duration(host.current_uptime()))),
+            separator(),
+            Span::raw("load"),
+        ];
+        // Colour each load figure by how busy it would keep the available threads.
+        let threads = host.logical_cores.max(1) as f64;
+        for load in host.load_average {
+            spans.push(Span::styled(
+                format!(" {load:.2}"),
+                Style::default().fg(usage_color((load / threads * 100.0) as f32)),
+            ));
+        }
+        spans.extend([
+            separator(),
+            Span::raw(format!("{} ({cores})", host.cpu_brand)),
+        ]);
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
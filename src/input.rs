@@ -0,0 +1,107 @@
+use std::{
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crossterm::{
+    event::{
+        self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, KeyEventKind, MouseEventKind,
+    },
+    execute,
+};
+
+use crate::Event;
+
+/// How long the reader blocks waiting for input before checking whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Ask the terminal to report mouse, paste and focus events alongside key presses.
+pub(crate) fn enable_terminal_events() -> io::Result<()> {
+    execute!(
+        io::stdout(),
+        EnableMouseCapture,
+        EnableBracketedPaste,
+        EnableFocusChange
+    )
+}
+
+pub(crate) fn disable_terminal_events() -> io::Result<()> {
+    execute!(
+        io::stdout(),
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        DisableFocusChange
+    )
+}
+
+/// Background thread forwarding terminal input to the app's event channel.
+#[derive(Debug)]
+pub(crate) struct InputReader {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl InputReader {
+    /// Start reading terminal events until [`InputReader::stop`] is called, the receiving
+    /// end of `tx` is dropped, or the terminal can no longer be read.
+    pub(crate) fn spawn(tx: Sender<Event>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = thread::spawn({
+            let stop = Arc::clone(&stop);
+            move || read_events(&tx, &stop)
+        });
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Signal the thread to finish and wait for it, so nothing reads stdin once the
+    /// terminal has been restored.
+    pub(crate) fn stop(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn read_events(tx: &Sender<Event>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match event::poll(POLL_INTERVAL) {
+            Ok(false) => continue,
+            Ok(true) => {}
+            Err(_) => break,
+        }
+        let Ok(terminal_event) = event::read() else {
+            break;
+        };
+        let event = match terminal_event {
+            // Only some platforms report releases; acting on them would double every key.
+            event::Event::Key(key) if key.kind == KeyEventKind::Release => continue,
+            event::Event::Key(key) => Event::Key(key),
+            // Mouse capture reports every pointer motion; nothing reacts to it, and each
+            // event would cut the frame wait short and redraw.
+            event::Event::Mouse(mouse)
+                if matches!(mouse.kind, MouseEventKind::Moved | MouseEventKind::Drag(_)) =>
+            {
+                continue;
+            }
+            event::Event::Mouse(mouse) => Event::Mouse(mouse),
+            event::Event::Resize(width, height) => Event::Resize(width, height),
+            event::Event::Paste(text) => Event::Paste(text),
+            event::Event::FocusGained => Event::FocusGained,
+            event::Event::FocusLost => Event::FocusLost,
+        };
+        if tx.send(event).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect, Size},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// Smallest terminal the dashboard is drawn in; below this only a notice is shown.
+pub(crate) const MIN_SIZE: Size = Size {
+    width: 60,
+    height: 20,
+};
+
+/// Terminals narrower than this get the single-column layout.
+const COMPACT_BELOW: u16 = 80;
+
+/// Terminals at least this wide (and tall) get the grid layout.
+const WIDE_FROM: Size = Size {
+    width: 180,
+    height: 45,
+};
+
+/// Which arrangement of panels suits the current terminal size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LayoutMode {
+    TooSmall,
+    /// One column: summary panels stacked above the process table.
+    Compact,
+    Standard,
+    /// Three panels per row with the process table below.
+    Wide,
+}
+
+impl LayoutMode {
+    pub(crate) fn for_size(size: Size) -> Self {
+        if size.width < MIN_SIZE.width || size.height < MIN_SIZE.height {
+            LayoutMode::TooSmall
+        } else if size.width < COMPACT_BELOW {
+            LayoutMode::Compact
+        } else if size.width >= WIDE_FROM.width && size.height >= WIDE_FROM.height {
+            LayoutMode::Wide
+        } else {
+            LayoutMode::Standard
+        }
+    }
+}
+
+/// Centered notice explaining why nothing else is drawn.
+pub(crate) fn render_too_small(area: Rect, frame: &mut Frame) {
+    let lines = vec![
+        Line::from("terminal too small").style(Style::default().add_modifier(Modifier::BOLD)),
+        Line::from(format!("{}x{}", area.width, area.height))
+            .style(Style::default().fg(Color::Red)),
+        Line::from(format!(
+            "need at least {}x{}",
+            MIN_SIZE.width, MIN_SIZE.height
+        )),
+    ];
+    let [area] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)])
+        .flex(Flex::Center)
+        .areas(area);
+    let notice = Paragraph::new(lines)
+        .centered()
+        .block(Block::new().borders(Borders::TOP | Borders::BOTTOM));
+    frame.render_widget(notice, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(width: u16, height: u16) -> LayoutMode {
+        LayoutMode::for_size(Size { width, height })
+    }
+
+    #[test]
+    fn modes_follow_terminal_size() {
+        assert_eq!(mode(59, 40), LayoutMode::TooSmall);
+        assert_eq!(mode(120, 19), LayoutMode::TooSmall);
+        assert_eq!(mode(60, 20), LayoutMode::Compact);
+        assert_eq!(mode(79, 50), LayoutMode::Compact);
+        assert_eq!(mode(80, 24), LayoutMode::Standard);
+        assert_eq!(mode(200, 30), LayoutMode::Standard);
+        assert_eq!(mode(180, 45), LayoutMode::Wide);
+    }
+}
@@ -1,62 +1,191 @@
+mod batch;
+mod cli;
+mod config;
+mod cpu;
+mod dashboard;
+mod disk;
+mod diskio;
+mod exporter;
+mod format;
+mod history;
+mod host;
+mod input;
+mod layout;
+mod memory;
+mod network;
+mod process;
+mod recording;
+mod sampler;
+mod sensors;
+mod signal;
+mod snapshot;
+mod tabs;
+mod theme;
+
 use std::{
-    sync::mpsc::{self, Receiver},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use color_eyre::{Result, eyre::Ok};
-use crossterm::event::{KeyCode, KeyEvent};
+use clap::Parser;
+use cli::{Cli, Command, TuiArgs};
+use color_eyre::Result;
+use config::Config;
+use cpu::{CpuPanel, CpuSnapshot};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use dashboard::{Dashboards, Widget};
+use disk::{DiskInfo, DiskPanel};
+use diskio::{DeviceIo, DiskIoPanel};
+use history::HistoryStore;
+use host::{HOST_REFRESH, HostHeader, HostInfo};
+use input::InputReader;
+use layout::LayoutMode;
+use memory::{MemoryPanel, MemorySnapshot};
+use network::{InterfaceInfo, NetworkPanel};
+use process::{ProcessFilter, ProcessInfo, ProcessTable, SortColumn};
 use ratatui::{
     DefaultTerminal, Frame,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     style::Style,
     widgets::{Block, Borders, Paragraph},
 };
-use sysinfo::System;
+use recording::{Player, Recording};
+use sampler::Sampler;
+use sensors::{SensorInfo, SensorPanel};
+use signal::{SIGNALS, SignalPopup};
+use sysinfo::{CpuRefreshKind, RefreshKind, System};
+use tabs::{Tab, TabBar};
 
 fn main() -> color_eyre::Result<()> {
-    let (event_tx, event_rx) = mpsc::channel::<Event>();
-    let tx_to_input_events = event_tx.clone();
-    thread::spawn(move || {
-        handle_input_events(tx_to_input_events);
-    });
-    let tx_to_input_events = event_tx.clone();
-    thread::spawn(move || {
-        handle_key_events(tx_to_input_events);
-    });
+    let cli = Cli::parse();
     color_eyre::install()?;
-    let terminal = ratatui::init();
-    let result = App::new().run(terminal, &event_rx);
-    ratatui::restore();
-    result
+    if cli.print_default_config {
+        print!("{}", config::DEFAULT_CONFIG);
+        return Ok(());
+    }
+    let mut config = Config::load(cli.config.as_deref())?;
+    config.apply_cli(&cli);
+    format::init(config.format);
+    theme::init(config.theme);
+    match cli.command.unwrap_or(Command::Tui(cli.tui)) {
+        Command::Tui(args) => run_tui(config, &args),
+        Command::Snapshot(args) => {
+            let interval = args
+                .interval
+                .map_or(config.refresh_interval, Duration::from_millis);
+            snapshot::run(&args, interval)
+        }
+        Command::Batch(args) => {
+            let interval = args
+                .interval
+                .map_or(config.refresh_interval, Duration::from_millis);
+            batch::run(&args, interval)
+        }
+        Command::Exporter(args) => {
+            let interval = args
+                .interval
+                .map_or(config.refresh_interval, Duration::from_millis);
+            exporter::run(&args, interval)
+        }
+        Command::Record(args) => {
+            let interval = args
+                .interval
+                .map_or(config.refresh_interval, Duration::from_millis);
+            recording::record(&args, interval)
+        }
+    }
 }
 
-fn handle_key_events(tx_to_input_events: mpsc::Sender<Event>) {
-    if let crossterm::event::Event::Key(key_event) = crossterm::event::read().unwrap() {
-        tx_to_input_events.send(Event::Input(key_event)).unwrap()
+fn run_tui(mut config: Config, args: &TuiArgs) -> Result<()> {
+    config.apply_tui_args(args)?;
+    let refresh_interval = config.refresh_interval;
+    let (event_tx, event_rx) = mpsc::channel::<Event>();
+    let mut app = App::new()
+        .with_config(config)
+        .with_tab(args.tab)
+        .with_filter(args.processes.filter())
+        .with_colors(args.color.enabled());
+    // The panels are fed either by the samplers below or by a recording played back.
+    if let Some(path) = &args.replay {
+        app = app.with_replay(Player::new(Recording::load(path)?));
+    } else {
+        let tx_to_input_events = event_tx.clone();
+        thread::spawn(move || {
+            handle_input_events(tx_to_input_events, refresh_interval);
+        });
+        let tx_to_host_events = event_tx.clone();
+        thread::spawn(move || {
+            handle_host_events(tx_to_host_events);
+        });
     }
+    let terminal = ratatui::init();
+    // Whatever fails from here on, the terminal is restored before the error is returned.
+    let result = input::enable_terminal_events()
+        .map_err(Into::into)
+        .and_then(|()| {
+            let input = InputReader::spawn(event_tx.clone());
+            let result = app.run(terminal, &event_rx);
+            input.stop();
+            result
+        });
+    let disabled = input::disable_terminal_events();
+    ratatui::restore();
+    result?;
+    Ok(disabled?)
 }
 
-fn handle_input_events(tx_to_input_events: mpsc::Sender<Event>) {
-    let mut sys = System::new_all();
+/// Host details change rarely, so they are sampled on their own slower loop.
+fn handle_host_events(tx_to_host_events: mpsc::Sender<Event>) {
+    let sys =
+        System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing()));
     loop {
-        sys.refresh_all();
-        let free_memory = sys.free_memory();
-        let cpu_usage = sys.global_cpu_usage();
-        if tx_to_input_events.send(Event::Memory(free_memory)).is_err() {
+        if tx_to_host_events
+            .send(Event::Host(HostInfo::sample(&sys)))
+            .is_err()
+        {
             break;
         }
-        if tx_to_input_events.send(Event::CPU(cpu_usage)).is_err() {
-            break;
+        thread::sleep(HOST_REFRESH);
+    }
+}
+
+fn handle_input_events(tx_to_input_events: mpsc::Sender<Event>, refresh_interval: Duration) {
+    let mut sampler = Sampler::new();
+    loop {
+        for event in sampler.sample().into_events() {
+            if tx_to_input_events.send(event).is_err() {
+                return;
+            }
         }
-        thread::sleep(Duration::from_millis(500));
+        thread::sleep(refresh_interval);
     }
 }
 
+/// Number of rows PageUp/PageDown move the process table selection by.
+const PAGE_ROWS: u16 = 10;
+
 pub(crate) enum Event {
-    Input(crossterm::event::KeyEvent), // crossterm key input event
-    Memory(u64),
-    CPU(f32),
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    /// New terminal size in columns and rows.
+    #[allow(
+        dead_code,
+        reason = "ratatui resizes on the next draw without being told"
+    )]
+    Resize(u16, u16),
+    #[allow(dead_code, reason = "nothing accepts text input yet")]
+    Paste(String),
+    FocusGained,
+    FocusLost,
+    Memory(MemorySnapshot),
+    Cpu(CpuSnapshot),
+    Processes(Vec<ProcessInfo>),
+    Network(Vec<InterfaceInfo>),
+    Disks(Vec<DiskInfo>),
+    DiskIo(Vec<DeviceIo>),
+    Sensors(Vec<SensorInfo>),
+    Host(HostInfo),
 }
 
 /// The main application which holds the state and logic of the application.
@@ -64,8 +193,36 @@ pub(crate) enum Event {
 pub struct App {
     /// Is the application running?
     running: bool,
-    latest_mem: Option<u64>,
-    latest_cpu: Option<f32>,
+    memory_panel: MemoryPanel,
+    cpu_panel: CpuPanel,
+    network_panel: NetworkPanel,
+    disk_panel: DiskPanel,
+    disk_io_panel: DiskIoPanel,
+    sensor_panel: SensorPanel,
+    host_header: HostHeader,
+    /// The screen currently shown below the tab bar.
+    tab: Tab,
+    tab_bar: TabBar,
+    /// How the overview tab arranges its panels.
+    dashboards: Dashboards,
+    /// Longest time between redraws when no input arrives.
+    frame_interval: Duration,
+    /// Whether frames keep their colors; off for `--color never` and NO_COLOR.
+    colors: bool,
+    history: HistoryStore,
+    process_table: ProcessTable,
+    /// Open "send signal" dialog, which captures all key input while shown.
+    signal_popup: Option<SignalPopup>,
+    /// Outcome of the last user action, shown on the bottom line.
+    status: Option<String>,
+    /// Recording being played back in place of live samples.
+    player: Option<Player>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
@@ -73,58 +230,389 @@ impl App {
     pub fn new() -> Self {
         Self {
             running: true,
-            latest_mem: None,
-            latest_cpu: None,
+            memory_panel: MemoryPanel::default(),
+            cpu_panel: CpuPanel::default(),
+            network_panel: NetworkPanel::default(),
+            disk_panel: DiskPanel::default(),
+            disk_io_panel: DiskIoPanel::default(),
+            sensor_panel: SensorPanel::default(),
+            host_header: HostHeader::default(),
+            tab: Tab::default(),
+            tab_bar: TabBar::default(),
+            dashboards: Dashboards::default(),
+            frame_interval: Config::default().frame_interval,
+            colors: true,
+            history: HistoryStore::default(),
+            process_table: ProcessTable::default(),
+            signal_popup: None,
+            status: None,
+            player: None,
         }
     }
 
+    /// Apply the frame interval and overview layout from `config`.
+    pub(crate) fn with_config(mut self, config: Config) -> Self {
+        self.frame_interval = config.frame_interval;
+        self.dashboards = Dashboards::new(config.layout);
+        self
+    }
+
+    pub(crate) fn with_tab(mut self, tab: Tab) -> Self {
+        self.tab = tab;
+        self
+    }
+
+    /// Only list the processes `filter` matches.
+    pub(crate) fn with_filter(mut self, filter: ProcessFilter) -> Self {
+        self.process_table.set_filter(filter);
+        self
+    }
+
+    pub(crate) fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Show `player`'s recording instead of waiting for live samples.
+    pub(crate) fn with_replay(mut self, player: Player) -> Self {
+        self.player = Some(player);
+        self.show_recorded(0);
+        self
+    }
+
     /// Run the application's main loop.
     fn run(mut self, mut terminal: DefaultTerminal, evt: &Receiver<Event>) -> Result<()> {
         self.running = true;
         while self.running {
-            terminal.draw(|frame| self.render(frame, &evt))?;
-            std::thread::sleep(std::time::Duration::from_millis(100));
+            terminal.draw(|frame| self.render(frame))?;
+            self.wait_for_events(evt);
+            self.advance_replay();
         }
         Ok(())
     }
 
-    fn render(&mut self, frame: &mut Frame, evt: &Receiver<Event>) {
-        // Process all available events to ensure we don't miss memory updates
-        for event in evt.try_iter() {
-            match event {
-                Event::Memory(mem) => self.latest_mem = Some(mem),
-                Event::Input(key_event) => self.on_key_event(key_event),
-                Event::CPU(cpu) => self.latest_cpu = Some(cpu),
+    /// Show whichever recorded samples playback has reached since the last frame.
+    fn advance_replay(&mut self) {
+        while let Some(index) = self.player.as_mut().and_then(Player::advance) {
+            self.apply_recorded(index, true);
+        }
+    }
+
+    /// Jump to the recorded sample at `index`. The history is replayed from the oldest
+    /// sample it still holds so the charts look as they did at the time.
+    fn show_recorded(&mut self, index: usize) {
+        let Some(player) = &self.player else {
+            return;
+        };
+        let start = player.history_start(index);
+        self.history.clear();
+        for earlier in start..index {
+            self.apply_recorded(earlier, false);
+        }
+        self.apply_recorded(index, true);
+    }
+
+    fn apply_recorded(&mut self, index: usize, with_processes: bool) {
+        let Some(player) = &self.player else {
+            return;
+        };
+        self.history.freeze(player.instant(index));
+        for event in player.events(index, with_processes) {
+            self.on_event(event);
+        }
+    }
+
+    /// Apply incoming events until the next frame is due. Input cuts the wait short so
+    /// key presses and resizes show up immediately rather than on the next tick.
+    fn wait_for_events(&mut self, evt: &Receiver<Event>) {
+        let deadline = Instant::now() + self.frame_interval;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let event = match evt.recv_timeout(timeout) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => return,
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.quit();
+                    return;
+                }
+            };
+            let is_input = matches!(
+                event,
+                Event::Key(_) | Event::Mouse(_) | Event::Resize(..) | Event::Paste(_)
+            );
+            self.on_event(event);
+            if is_input {
+                // Pick up whatever else already arrived before redrawing.
+                for event in evt.try_iter() {
+                    self.on_event(event);
+                }
+                return;
             }
         }
-        let layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(frame.area());
+    }
+
+    fn on_event(&mut self, event: Event) {
+        match event {
+            Event::Memory(memory) => self.memory_panel.update(memory, &mut self.history),
+            Event::Key(key_event) => self.on_key_event(key_event),
+            Event::Mouse(mouse_event) => self.on_mouse_event(mouse_event),
+            Event::Resize(..) | Event::Paste(_) | Event::FocusGained | Event::FocusLost => {}
+            Event::Cpu(cpu) => self.cpu_panel.update(cpu, &mut self.history),
+            Event::Processes(processes) => self.process_table.set_processes(processes),
+            Event::Network(interfaces) => self.network_panel.update(interfaces, &mut self.history),
+            Event::Disks(disks) => self.disk_panel.update(disks),
+            Event::DiskIo(devices) => self.disk_io_panel.update(devices, &mut self.history),
+            Event::Sensors(sensors) => self.sensor_panel.update(sensors, &mut self.history),
+            Event::Host(host) => self.host_header.update(host),
+        }
+    }
 
-        if let Some(mem) = &self.latest_mem {
-            self.render_col(mem, layout[0], frame, "memory info");
+    fn render(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let mode = LayoutMode::for_size(area.as_size());
+        if mode == LayoutMode::TooSmall {
+            layout::render_too_small(area, frame);
+            return;
+        }
+        let [header, tab_bar, body, status] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        self.host_header.render(header, frame);
+        self.tab_bar.render(self.tab, tab_bar, frame);
+        match self.tab {
+            Tab::Overview => {
+                for (widget, area) in self.dashboards.for_mode(mode).areas(body) {
+                    self.render_widget(widget, area, frame);
+                }
+            }
+            Tab::Processes => self.process_table.render(body, frame),
+            Tab::Network => self.network_panel.render(&self.history, body, frame),
+            Tab::Disks => {
+                let [disks, disk_io] =
+                    Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .areas(body);
+                self.disk_panel.render(disks, frame);
+                self.disk_io_panel.render(&self.history, disk_io, frame);
+            }
+            Tab::Sensors => self.sensor_panel.render(&self.history, body, frame),
+        }
+        if let Some(status_text) = &self.status {
+            frame.render_widget(Paragraph::new(status_text.as_str()), status);
+        } else if let Some(player) = &self.player {
+            frame.render_widget(Paragraph::new(player.status()), status);
         }
-        if let Some(cpu) = self.latest_cpu {
-            self.render_col(cpu, layout[1], frame, "cpu info");
+        if let Some(popup) = &mut self.signal_popup {
+            popup.render(frame);
+        }
+        if !self.colors {
+            theme::strip_colors(frame.buffer_mut());
         }
     }
 
-    fn render_col(&self, mem: impl ToString, area: Rect, frame: &mut Frame, header: &str) {
-        let block = Block::new()
-            .title(header)
-            .borders(Borders::ALL)
-            .style(Style::default().fg(ratatui::style::Color::Red));
+    fn render_widget(&mut self, widget: Widget, area: Rect, frame: &mut Frame) {
+        match widget {
+            Widget::Memory => self.memory_panel.render(area, frame),
+            Widget::Cpu => self.cpu_panel.render(&self.history, area, frame),
+            Widget::CpuHistory => self.render_cpu_history(area, frame),
+            Widget::MemoryHistory => self.render_memory_history(area, frame),
+            Widget::Network => self.network_panel.render(&self.history, area, frame),
+            Widget::Disks => self.disk_panel.render(area, frame),
+            Widget::DiskIo => self.disk_io_panel.render(&self.history, area, frame),
+            Widget::Sensors => self.sensor_panel.render(&self.history, area, frame),
+            Widget::Processes => self.process_table.render(area, frame),
+        }
+    }
 
-        let paragraph = Paragraph::new(mem.to_string()).block(block);
+    fn render_cpu_history(&self, area: Rect, frame: &mut Frame) {
+        self.history.render_chart(
+            "cpu usage",
+            &[("cpu", "total", theme::current().cpu)],
+            100.0,
+            "%",
+            area,
+            frame,
+        );
+    }
 
-        frame.render_widget(paragraph, area);
+    fn render_memory_history(&self, area: Rect, frame: &mut Frame) {
+        let total = self
+            .memory_panel
+            .latest()
+            .map_or(1.0, |memory| memory.total.max(1) as f64);
+        let block = Block::new()
+            .title(format!(
+                "used memory (last {})",
+                self.history.window.label()
+            ))
+            .borders(Borders::ALL);
+        let sparkline = self
+            .history
+            .sparkline("memory.used", total, block.inner(area).width)
+            .style(Style::default().fg(theme::current().memory))
+            .block(block);
+        frame.render_widget(sparkline, area);
     }
 
     /// Handles the key events and updates the state of [`App`].
     fn on_key_event(&mut self, key: KeyEvent) {
-        if let (_, KeyCode::Esc | KeyCode::Char('q')) = (key.modifiers, key.code) {
-            self.quit()
+        if self.signal_popup.is_some() {
+            self.on_signal_popup_key(key);
+            return;
+        }
+        if self.player.is_some() && self.on_replay_key(key) {
+            return;
+        }
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc | KeyCode::Char('q')) => self.quit(),
+            (_, KeyCode::Tab) => self.tab = self.tab.next(),
+            (_, KeyCode::BackTab) => self.tab = self.tab.previous(),
+            (_, KeyCode::Char(digit @ '1'..='9')) => {
+                if let Some(tab) = Tab::from_digit(digit) {
+                    self.tab = tab;
+                }
+            }
+            (_, KeyCode::Char('v')) => self.network_panel.toggle_virtual(),
+            (_, KeyCode::Char('w')) => self.history.window = self.history.window.next(),
+            // The table is not drawn on the other tabs, so its keys would act unseen.
+            _ if self.tab.shows_processes() => self.on_process_key(key),
+            _ => {}
+        }
+    }
+
+    /// Handles the keys that move through, sort and act on the process table.
+    fn on_process_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => self.process_table.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.process_table.select_previous(),
+            KeyCode::Home | KeyCode::Char('g') => self.process_table.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.process_table.select_last(),
+            KeyCode::PageDown => self.process_table.page_down(PAGE_ROWS),
+            KeyCode::PageUp => self.process_table.page_up(PAGE_ROWS),
+            KeyCode::Char('>') => self.process_table.sort_next_column(),
+            KeyCode::Char('<') => self.process_table.sort_previous_column(),
+            KeyCode::Char('I') => self.process_table.reverse_sort(),
+            KeyCode::Char('P') => self.process_table.sort_by(SortColumn::Cpu),
+            KeyCode::Char('M') => self.process_table.sort_by(SortColumn::Memory),
+            KeyCode::Char('N') => self.process_table.sort_by(SortColumn::Pid),
+            KeyCode::Char('T') => self.process_table.sort_by(SortColumn::RunTime),
+            KeyCode::Char('o') => self.process_table.toggle_io_mode(),
+            KeyCode::F(5) | KeyCode::Char('t') => self.process_table.toggle_tree(),
+            KeyCode::Left | KeyCode::Char('-') => self.process_table.collapse_selected(),
+            KeyCode::Right | KeyCode::Char('+') => self.process_table.expand_selected(),
+            KeyCode::F(9) | KeyCode::Char('x') => {
+                if let Some(process) = self.process_table.selected() {
+                    self.signal_popup = Some(SignalPopup::new(
+                        process.pid,
+                        process.start_time,
+                        process.name.clone(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles the playback keys while replaying, returning whether `key` was one.
+    /// Signals are refused: the recorded PIDs may belong to other processes by now.
+    fn on_replay_key(&mut self, key: KeyEvent) -> bool {
+        let Some(player) = &mut self.player else {
+            return false;
+        };
+        let position = player.position();
+        let target = match key.code {
+            KeyCode::Char(' ') => {
+                player.toggle_playing();
+                player.position()
+            }
+            KeyCode::Char(',') => player.step(-1),
+            KeyCode::Char('.') => player.step(1),
+            KeyCode::Char('[') => player.jump(false),
+            KeyCode::Char(']') => player.jump(true),
+            KeyCode::Char('f') => {
+                player.faster();
+                position
+            }
+            KeyCode::F(9) | KeyCode::Char('x') if self.tab.shows_processes() => {
+                self.status = Some("signals are disabled while replaying".to_string());
+                return true;
+            }
+            _ => return false,
+        };
+        self.status = None;
+        if target == position + 1 {
+            self.apply_recorded(target, true);
+        } else if target != position {
+            self.show_recorded(target);
+        }
+        true
+    }
+
+    /// Handles mouse clicks and wheel scrolling. The signal dialog only takes the wheel.
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+        if let Some(popup) = &mut self.signal_popup {
+            if let SignalPopup::Menu { .. } = popup {
+                match mouse.kind {
+                    MouseEventKind::ScrollDown => popup.select_next(),
+                    MouseEventKind::ScrollUp => popup.select_previous(),
+                    _ => {}
+                }
+            }
+            return;
+        }
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind
+            && let Some(tab) = self.tab_bar.tab_at(mouse.column, mouse.row)
+        {
+            self.tab = tab;
+            return;
+        }
+        // The table keeps the area it was last drawn in, which is stale on other tabs.
+        if !self.tab.shows_processes() || !self.process_table.contains(position) {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.process_table.click(position),
+            MouseEventKind::ScrollDown => self.process_table.scroll(true),
+            MouseEventKind::ScrollUp => self.process_table.scroll(false),
+            _ => {}
+        }
+    }
+
+    /// Handles key events while the signal dialog is open.
+    fn on_signal_popup_key(&mut self, key: KeyEvent) {
+        let Some(popup) = &mut self.signal_popup else {
+            return;
+        };
+        match popup {
+            SignalPopup::Menu { .. } => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.signal_popup = None,
+                KeyCode::Down | KeyCode::Char('j') => popup.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => popup.select_previous(),
+                KeyCode::Enter => popup.choose(),
+                _ => {}
+            },
+            SignalPopup::Confirm {
+                pid,
+                start_time,
+                signal,
+                ..
+            } => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    let (label, signal) = SIGNALS[*signal];
+                    let pid = *pid;
+                    self.status = Some(match signal::send_signal(pid, *start_time, signal) {
+                        Ok(()) => format!("sent {label} to {pid}"),
+                        Err(err) => err,
+                    });
+                    self.signal_popup = None;
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.signal_popup = None,
+                _ => {}
+            },
         }
     }
 
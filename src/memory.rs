@@ -0,0 +1,221 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Gauge, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+use crate::{cpu::usage_color, format, history::HistoryStore, theme};
+
+/// RAM and swap usage at one instant, in bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MemorySnapshot {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    pub free: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    /// Kernel breakdown from /proc/meminfo; `None` where that file does not exist.
+    pub details: Option<MemInfo>,
+}
+
+/// The subset of /proc/meminfo that `sysinfo` does not expose, converted to bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct MemInfo {
+    pub buffers: u64,
+    pub cached: u64,
+    pub dirty: u64,
+    pub slab: u64,
+    pub huge_pages_total: u64,
+    pub huge_pages_free: u64,
+    pub huge_page_size: u64,
+}
+
+impl MemInfo {
+    pub(crate) fn read() -> Option<Self> {
+        std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .map(|contents| Self::parse(&contents))
+    }
+
+    /// Parse the `Key:   value [kB]` lines of /proc/meminfo, ignoring unknown keys.
+    pub(crate) fn parse(contents: &str) -> Self {
+        let mut info = MemInfo::default();
+        for line in contents.lines() {
+            let Some((key, rest)) = line.split_once(':') else {
+                continue;
+            };
+            let mut fields = rest.split_whitespace();
+            let Some(value) = fields.next().and_then(|v| v.parse::<u64>().ok()) else {
+                continue;
+            };
+            let bytes = match fields.next() {
+                Some("kB") => value * 1024,
+                _ => value,
+            };
+            match key {
+                "Buffers" => info.buffers = bytes,
+                "Cached" => info.cached = bytes,
+                "Dirty" => info.dirty = bytes,
+                "Slab" => info.slab = bytes,
+                "HugePages_Total" => info.huge_pages_total = bytes,
+                "HugePages_Free" => info.huge_pages_free = bytes,
+                "Hugepagesize" => info.huge_page_size = bytes,
+                _ => {}
+            }
+        }
+        info
+    }
+}
+
+impl MemorySnapshot {
+    pub(crate) fn sample(sys: &System) -> Self {
+        Self {
+            total: sys.total_memory(),
+            used: sys.used_memory(),
+            available: sys.available_memory(),
+            free: sys.free_memory(),
+            swap_total: sys.total_swap(),
+            swap_used: sys.used_swap(),
+            details: MemInfo::read(),
+        }
+    }
+}
+
+/// RAM and swap gauges followed by a breakdown of where the memory went.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryPanel {
+    latest: Option<MemorySnapshot>,
+}
+
+impl MemoryPanel {
+    /// Store the snapshot for display and append used memory to `history`.
+    pub(crate) fn update(&mut self, snapshot: MemorySnapshot, history: &mut HistoryStore) {
+        let now = history.now();
+        history.record("memory.used", now, snapshot.used as f64);
+        self.latest = Some(snapshot);
+    }
+
+    pub(crate) fn latest(&self) -> Option<&MemorySnapshot> {
+        self.latest.as_ref()
+    }
+
+    pub(crate) fn render(&self, area: Rect, frame: &mut Frame) {
+        let Some(snapshot) = &self.latest else {
+            return;
+        };
+        let block = Block::new()
+            .title("memory info")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(theme::current().border));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [ram_area, swap_area, details_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+        render_gauge("RAM", snapshot.used, snapshot.total, ram_area, frame);
+        render_gauge(
+            "swap",
+            snapshot.swap_used,
+            snapshot.swap_total,
+            swap_area,
+            frame,
+        );
+
+        let mut lines = vec![
+            Line::from(format!(
+                "total {}  used {}  avail {}  free {}",
+                format::bytes(snapshot.total),
+                format::bytes(snapshot.used),
+                format::bytes(snapshot.available),
+                format::bytes(snapshot.free),
+            )),
+            Line::from(format!(
+                "swap total {}  used {}",
+                format::bytes(snapshot.swap_total),
+                format::bytes(snapshot.swap_used),
+            )),
+        ];
+        if let Some(details) = &snapshot.details {
+            lines.push(Line::from(format!(
+                "buffers {}  cached {}  dirty {}  slab {}",
+                format::bytes(details.buffers),
+                format::bytes(details.cached),
+                format::bytes(details.dirty),
+                format::bytes(details.slab),
+            )));
+            if details.huge_pages_total > 0 {
+                lines.push(Line::from(format!(
+                    "hugepages {}/{} free ({} each)",
+                    details.huge_pages_free,
+                    details.huge_pages_total,
+                    format::bytes(details.huge_page_size),
+                )));
+            }
+        }
+        frame.render_widget(Paragraph::new(lines), details_area);
+    }
+}
+
+fn render_gauge(label: &str, used: u64, total: u64, area: Rect, frame: &mut Frame) {
+    let ratio = if total == 0 {
+        0.0
+    } else {
+        (used as f64 / total as f64).clamp(0.0, 1.0)
+    };
+    let gauge = Gauge::default()
+        .label(format!(
+            "{label} {} / {}",
+            format::bytes(used),
+            format::bytes(total)
+        ))
+        .ratio(ratio)
+        .gauge_style(Style::default().fg(usage_color((ratio * 100.0) as f32)));
+    frame.render_widget(gauge, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_meminfo_fields_it_shows() {
+        let meminfo = "\
+MemTotal:       16318420 kB
+Buffers:          204800 kB
+Cached:          4096000 kB
+SwapCached:         1024 kB
+Dirty:               512 kB
+Slab:             819200 kB
+HugePages_Total:       4
+HugePages_Free:        1
+Hugepagesize:       2048 kB
+DirectMap1G:    garbage
+";
+        assert_eq!(
+            MemInfo::parse(meminfo),
+            MemInfo {
+                buffers: 204_800 * 1024,
+                cached: 4_096_000 * 1024,
+                dirty: 512 * 1024,
+                slab: 819_200 * 1024,
+                huge_pages_total: 4,
+                huge_pages_free: 1,
+                huge_page_size: 2048 * 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn empty_meminfo_is_all_zero() {
+        assert_eq!(MemInfo::parse(""), MemInfo::default());
+    }
+}
@@ -0,0 +1,177 @@
+use std::{path::Path, time::Duration};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Row, Table},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::Networks;
+
+use crate::{
+    format,
+    history::{HistoryStore, TREND_PAIR_WIDTH},
+    theme,
+};
+
+/// Traffic and addressing for one network interface over the last sample interval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct InterfaceInfo {
+    pub name: String,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    pub rx_packet_rate: f64,
+    pub tx_packet_rate: f64,
+    /// Receive and transmit errors since the interface came up.
+    pub errors: u64,
+    pub mac: String,
+    pub addresses: Vec<String>,
+    pub loopback: bool,
+    pub is_virtual: bool,
+}
+
+impl InterfaceInfo {
+    /// Describe every interface in `networks`, sorted by name. `elapsed` is the time since
+    /// the previous refresh and turns per-refresh counters into rates.
+    pub(crate) fn snapshot(networks: &Networks, elapsed: Duration) -> Vec<InterfaceInfo> {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let mut interfaces: Vec<InterfaceInfo> = networks
+            .list()
+            .iter()
+            .map(|(name, data)| InterfaceInfo {
+                name: name.clone(),
+                rx_rate: data.received() as f64 / seconds,
+                tx_rate: data.transmitted() as f64 / seconds,
+                rx_packet_rate: data.packets_received() as f64 / seconds,
+                tx_packet_rate: data.packets_transmitted() as f64 / seconds,
+                errors: data.total_errors_on_received() + data.total_errors_on_transmitted(),
+                mac: data.mac_address().to_string(),
+                addresses: data
+                    .ip_networks()
+                    .iter()
+                    .map(|network| network.to_string())
+                    .collect(),
+                loopback: is_loopback(name),
+                is_virtual: is_virtual(name),
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces
+    }
+}
+
+fn is_loopback(name: &str) -> bool {
+    name == "lo" || name.starts_with("lo0") || name.starts_with("Loopback")
+}
+
+/// Bridges, veths, tunnels and the like. Linux lists these under /sys/devices/virtual;
+/// elsewhere fall back to well-known name prefixes.
+fn is_virtual(name: &str) -> bool {
+    let sysfs = Path::new("/sys/devices/virtual/net");
+    if sysfs.is_dir() {
+        return sysfs.join(name).exists();
+    }
+    const PREFIXES: [&str; 9] = [
+        "docker", "veth", "br-", "virbr", "vmnet", "vboxnet", "tun", "tap", "utun",
+    ];
+    PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Per-interface throughput table with rx/tx sparklines.
+#[derive(Debug, Default)]
+pub(crate) struct NetworkPanel {
+    latest: Vec<InterfaceInfo>,
+    /// Leave out loopback and virtual interfaces.
+    hide_virtual: bool,
+}
+
+impl NetworkPanel {
+    /// Store the snapshot for display and append its rates to `history`.
+    pub(crate) fn update(&mut self, interfaces: Vec<InterfaceInfo>, history: &mut HistoryStore) {
+        let now = history.now();
+        for interface in &interfaces {
+            history.record(format!("net.{}.rx", interface.name), now, interface.rx_rate);
+            history.record(format!("net.{}.tx", interface.name), now, interface.tx_rate);
+        }
+        self.latest = interfaces;
+    }
+
+    pub(crate) fn toggle_virtual(&mut self) {
+        self.hide_virtual = !self.hide_virtual;
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &InterfaceInfo> {
+        self.latest.iter().filter(|interface| {
+            !(self.hide_virtual && (interface.loopback || interface.is_virtual))
+        })
+    }
+
+    pub(crate) fn render(&self, history: &HistoryStore, area: Rect, frame: &mut Frame) {
+        let block = Block::new()
+            .title(if self.hide_virtual {
+                "network (physical only)"
+            } else {
+                "network"
+            })
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [table_area, trend_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(TREND_PAIR_WIDTH)])
+                .areas(inner);
+
+        let header = Row::new([
+            "IFACE",
+            "RX/s",
+            "TX/s",
+            "PKT RX/s",
+            "PKT TX/s",
+            "ERRS",
+            "MAC",
+            "ADDRESSES",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.visible().map(|interface| {
+            Row::new([
+                interface.name.clone(),
+                format::rate(interface.rx_rate),
+                format::rate(interface.tx_rate),
+                format!("{:.0}", interface.rx_packet_rate),
+                format!("{:.0}", interface.tx_packet_rate),
+                interface.errors.to_string(),
+                interface.mac.clone(),
+                interface.addresses.join(" "),
+            ])
+        });
+        let widths = [
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(17),
+            Constraint::Min(10),
+        ];
+        frame.render_widget(Table::new(rows, widths).header(header), table_area);
+
+        let series: Vec<(String, String)> = self
+            .visible()
+            .map(|interface| {
+                (
+                    format!("net.{}.rx", interface.name),
+                    format!("net.{}.tx", interface.name),
+                )
+            })
+            .collect();
+        history.render_trend_pairs(
+            ("rx", theme::current().rx),
+            ("tx", theme::current().tx),
+            &series,
+            trend_area,
+            frame,
+        );
+    }
+}
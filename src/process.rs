@@ -0,0 +1,818 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Row, Table, TableState},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::{System, Users};
+
+use crate::{format, theme};
+
+/// A point-in-time copy of the fields the process table shows for one process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ProcessInfo {
+    pub pid: u32,
+    pub parent: Option<u32>,
+    pub name: String,
+    pub user: String,
+    pub cpu_usage: f32,
+    /// Resident set size in bytes.
+    pub memory: u64,
+    pub virtual_memory: u64,
+    pub status: String,
+    pub command: String,
+    /// Seconds since the epoch at which the process started.
+    pub start_time: u64,
+    /// Seconds the process has been running.
+    pub run_time: u64,
+    /// Bytes read from disk per second over the previous sample interval.
+    pub read_rate: f64,
+    /// Bytes written to disk per second over the previous sample interval.
+    pub write_rate: f64,
+}
+
+impl ProcessInfo {
+    /// Collect every process currently known to `sys`, ordered by PID. `elapsed` is the
+    /// time since the previous refresh and turns per-refresh byte counts into rates.
+    pub(crate) fn snapshot(sys: &System, users: &Users, elapsed: Duration) -> Vec<ProcessInfo> {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let mut processes: Vec<ProcessInfo> = sys
+            .processes()
+            .values()
+            .map(|process| {
+                let user = process
+                    .user_id()
+                    .and_then(|uid| users.get_user_by_id(uid))
+                    .map(|user| user.name().to_string())
+                    .unwrap_or_else(|| "?".to_string());
+                let command = if process.cmd().is_empty() {
+                    process.name().to_string_lossy().into_owned()
+                } else {
+                    process
+                        .cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                let disk_usage = process.disk_usage();
+                ProcessInfo {
+                    pid: process.pid().as_u32(),
+                    parent: process.parent().map(|pid| pid.as_u32()),
+                    name: process.name().to_string_lossy().into_owned(),
+                    user,
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    virtual_memory: process.virtual_memory(),
+                    status: process.status().to_string(),
+                    command,
+                    start_time: process.start_time(),
+                    run_time: process.run_time(),
+                    read_rate: disk_usage.read_bytes as f64 / seconds,
+                    write_rate: disk_usage.written_bytes as f64 / seconds,
+                }
+            })
+            .collect();
+        processes.sort_by_key(|process| process.pid);
+        processes
+    }
+
+    /// Combined read and write throughput in bytes per second.
+    pub(crate) fn io_rate(&self) -> f64 {
+        self.read_rate + self.write_rate
+    }
+}
+
+#[cfg(test)]
+impl ProcessInfo {
+    /// An idle root-owned process for tests to adjust.
+    pub(crate) fn example(pid: u32, name: &str) -> Self {
+        Self {
+            pid,
+            parent: None,
+            name: name.to_string(),
+            user: "root".to_string(),
+            cpu_usage: 0.0,
+            memory: 0,
+            virtual_memory: 0,
+            status: "Sleeping".to_string(),
+            command: String::new(),
+            start_time: 0,
+            run_time: 0,
+            read_rate: 0.0,
+            write_rate: 0.0,
+        }
+    }
+}
+
+/// Column the process table is ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SortColumn {
+    Pid,
+    Name,
+    #[default]
+    Cpu,
+    Memory,
+    RunTime,
+    DiskIo,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 6] = [
+        SortColumn::Pid,
+        SortColumn::Name,
+        SortColumn::Cpu,
+        SortColumn::Memory,
+        SortColumn::RunTime,
+        SortColumn::DiskIo,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SortColumn::Pid => "PID",
+            SortColumn::Name => "name",
+            SortColumn::Cpu => "CPU%",
+            SortColumn::Memory => "memory",
+            SortColumn::RunTime => "run time",
+            SortColumn::DiskIo => "disk I/O",
+        }
+    }
+
+    /// Whether the column is sorted largest-first when it is first selected.
+    fn descending_by_default(self) -> bool {
+        matches!(
+            self,
+            SortColumn::Cpu | SortColumn::Memory | SortColumn::RunTime | SortColumn::DiskIo
+        )
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Ascending comparison of two processes on this column, ties broken by PID so the
+    /// order never depends on the order sysinfo returned the processes in.
+    fn compare(self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        let ordering = match self {
+            SortColumn::Pid => Ordering::Equal,
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            SortColumn::Memory => a.memory.cmp(&b.memory),
+            SortColumn::RunTime => a.run_time.cmp(&b.run_time),
+            SortColumn::DiskIo => a.io_rate().total_cmp(&b.io_rate()),
+        };
+        ordering.then(a.pid.cmp(&b.pid))
+    }
+}
+
+/// Restricts which processes are listed, e.g. from `--filter` and `--pid`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProcessFilter {
+    /// Lowercased text the name or command line must contain.
+    text: Option<String>,
+    /// PIDs to keep; empty keeps every PID.
+    pids: Vec<u32>,
+}
+
+impl ProcessFilter {
+    pub(crate) fn new(text: Option<&str>, pids: &[u32]) -> Self {
+        Self {
+            text: text.filter(|text| !text.is_empty()).map(str::to_lowercase),
+            pids: pids.to_vec(),
+        }
+    }
+
+    pub(crate) fn matches(&self, process: &ProcessInfo) -> bool {
+        let pid_matches = self.pids.is_empty() || self.pids.contains(&process.pid);
+        let text_matches = self.text.as_ref().is_none_or(|text| {
+            process.name.to_lowercase().contains(text)
+                || process.command.to_lowercase().contains(text)
+        });
+        pid_matches && text_matches
+    }
+
+    /// Short description for the table title, empty when nothing is filtered.
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(text) = &self.text {
+            parts.push(format!("\"{text}\""));
+        }
+        match self.pids.len() {
+            0 => {}
+            1 => parts.push(format!("pid {}", self.pids[0])),
+            n => parts.push(format!("{n} pids")),
+        }
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!(" [filter: {}]", parts.join(", "))
+        }
+    }
+}
+
+/// Keep the processes `filter` matches, busiest first, at most `top` of them.
+pub(crate) fn keep_busiest(
+    processes: &mut Vec<ProcessInfo>,
+    filter: &ProcessFilter,
+    top: Option<usize>,
+) {
+    processes.retain(|process| filter.matches(process));
+    processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage).then(a.pid.cmp(&b.pid)));
+    if let Some(top) = top {
+        processes.truncate(top);
+    }
+}
+
+/// One line of the table: which process it shows and the tree guide drawn before its
+/// command. In flat mode every process gets a row with an empty guide.
+#[derive(Debug)]
+struct VisibleRow {
+    index: usize,
+    guide: String,
+}
+
+/// Scrollable, selectable table of processes.
+///
+/// The selection follows a PID rather than a row index, so a refresh that reorders the
+/// table keeps the same process highlighted.
+#[derive(Debug)]
+pub(crate) struct ProcessTable {
+    processes: Vec<ProcessInfo>,
+    rows: Vec<VisibleRow>,
+    state: TableState,
+    sort: SortColumn,
+    descending: bool,
+    /// Nest processes under their parents instead of listing them flat.
+    tree: bool,
+    /// PIDs whose children are hidden in tree mode.
+    collapsed: HashSet<u32>,
+    /// iotop-style view: sorted by disk I/O with idle processes hidden. Holds the sort
+    /// order to restore when the mode is switched off.
+    io_mode: Option<(SortColumn, bool)>,
+    /// Where the table was last drawn, for mapping mouse clicks to rows and columns.
+    area: Rect,
+    filter: ProcessFilter,
+}
+
+impl Default for ProcessTable {
+    fn default() -> Self {
+        let sort = SortColumn::default();
+        Self {
+            processes: Vec::new(),
+            rows: Vec::new(),
+            state: TableState::default(),
+            sort,
+            descending: sort.descending_by_default(),
+            tree: false,
+            collapsed: HashSet::new(),
+            io_mode: None,
+            area: Rect::default(),
+            filter: ProcessFilter::default(),
+        }
+    }
+}
+
+impl ProcessTable {
+    /// Replace the table contents with a fresh snapshot, keeping the selected PID.
+    pub(crate) fn set_processes(&mut self, mut processes: Vec<ProcessInfo>) {
+        let selected_pid = self.selected().map(|process| process.pid);
+        processes.retain(|process| self.filter.matches(process));
+        self.processes = processes;
+        self.sort_and_reselect(selected_pid);
+    }
+
+    /// List only the processes `filter` matches, from the next snapshot on.
+    pub(crate) fn set_filter(&mut self, filter: ProcessFilter) {
+        self.filter = filter;
+    }
+
+    /// The currently highlighted process, if any.
+    pub(crate) fn selected(&self) -> Option<&ProcessInfo> {
+        self.state
+            .selected()
+            .and_then(|row| self.rows.get(row))
+            .map(|row| &self.processes[row.index])
+    }
+
+    /// Switch between the flat list and the parent/child tree.
+    pub(crate) fn toggle_tree(&mut self) {
+        self.tree = !self.tree;
+        self.resort();
+    }
+
+    /// Toggle the "top I/O" view, which lists only processes doing disk I/O, busiest
+    /// first. The tree is not drawn while it is on.
+    pub(crate) fn toggle_io_mode(&mut self) {
+        match self.io_mode.take() {
+            Some((sort, descending)) => {
+                self.sort = sort;
+                self.descending = descending;
+            }
+            None => {
+                self.io_mode = Some((self.sort, self.descending));
+                self.sort = SortColumn::DiskIo;
+                self.descending = true;
+            }
+        }
+        self.resort();
+    }
+
+    /// Hide the children of the selected process (tree mode only).
+    pub(crate) fn collapse_selected(&mut self) {
+        if let Some(pid) = self.tree.then(|| self.selected().map(|p| p.pid)).flatten() {
+            self.collapsed.insert(pid);
+            self.resort();
+        }
+    }
+
+    /// Show the children of the selected process again (tree mode only).
+    pub(crate) fn expand_selected(&mut self) {
+        if let Some(pid) = self.tree.then(|| self.selected().map(|p| p.pid)).flatten() {
+            self.collapsed.remove(&pid);
+            self.resort();
+        }
+    }
+
+    /// Order by `column`, or flip the direction if it is already the sort column.
+    pub(crate) fn sort_by(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = column.descending_by_default();
+        }
+        self.resort();
+    }
+
+    pub(crate) fn sort_next_column(&mut self) {
+        self.sort_by(self.sort.next());
+    }
+
+    pub(crate) fn sort_previous_column(&mut self) {
+        self.sort_by(self.sort.previous());
+    }
+
+    pub(crate) fn reverse_sort(&mut self) {
+        self.descending = !self.descending;
+        self.resort();
+    }
+
+    fn resort(&mut self) {
+        let selected_pid = self.selected().map(|process| process.pid);
+        self.sort_and_reselect(selected_pid);
+    }
+
+    fn sort_and_reselect(&mut self, selected_pid: Option<u32>) {
+        let (sort, descending) = (self.sort, self.descending);
+        self.processes.sort_by(|a, b| {
+            let ordering = sort.compare(a, b);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.rows = if self.io_mode.is_some() {
+            (0..self.processes.len())
+                .filter(|index| self.processes[*index].io_rate() > 0.0)
+                .map(|index| VisibleRow {
+                    index,
+                    guide: String::new(),
+                })
+                .collect()
+        } else if self.tree {
+            tree_rows(&self.processes, &self.collapsed)
+        } else {
+            (0..self.processes.len())
+                .map(|index| VisibleRow {
+                    index,
+                    guide: String::new(),
+                })
+                .collect()
+        };
+        let index = selected_pid
+            .and_then(|pid| {
+                self.rows
+                    .iter()
+                    .position(|row| self.processes[row.index].pid == pid)
+            })
+            .or_else(|| self.state.selected());
+        self.select_index(index.unwrap_or(0));
+    }
+
+    fn select_index(&mut self, index: usize) {
+        if self.rows.is_empty() {
+            self.state.select(None);
+        } else {
+            self.state.select(Some(index.min(self.rows.len() - 1)));
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let current = self.state.selected().unwrap_or(0);
+        self.select_index(current.saturating_add_signed(delta));
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        self.move_selection(1);
+    }
+
+    pub(crate) fn select_previous(&mut self) {
+        self.move_selection(-1);
+    }
+
+    pub(crate) fn select_first(&mut self) {
+        self.select_index(0);
+    }
+
+    pub(crate) fn select_last(&mut self) {
+        self.select_index(usize::MAX);
+    }
+
+    pub(crate) fn page_down(&mut self, rows: u16) {
+        self.move_selection(rows as isize);
+    }
+
+    pub(crate) fn page_up(&mut self, rows: u16) {
+        self.move_selection(-(rows as isize));
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, frame: &mut Frame) {
+        let arrow = if self.descending { "▼" } else { "▲" };
+        let header = Row::new(COLUMNS.iter().enumerate().map(|(index, title)| {
+            if column_sort(index) == Some(self.sort) {
+                format!("{title}{arrow}")
+            } else {
+                title.to_string()
+            }
+        }))
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.rows.iter().map(|row| {
+            let process = &self.processes[row.index];
+            Row::new([
+                process.pid.to_string(),
+                process.user.clone(),
+                format::percent(process.cpu_usage as f64),
+                format::bytes(process.memory),
+                format::bytes(process.virtual_memory),
+                format::rate(process.read_rate),
+                format::rate(process.write_rate),
+                format::duration(Duration::from_secs(process.run_time)),
+                process.status.clone(),
+                format!("{}{}", row.guide, process.command),
+            ])
+        });
+        let table = Table::new(rows, COLUMN_WIDTHS)
+            .header(header)
+            .block(
+                Block::new()
+                    .title(format!(
+                        "processes ({}) sorted by {} {arrow}{}{}",
+                        self.rows.len(),
+                        self.sort.label(),
+                        match (self.io_mode.is_some(), self.tree) {
+                            (true, _) => " [top I/O]",
+                            (false, true) => " [tree]",
+                            (false, false) => "",
+                        },
+                        self.filter.describe()
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .row_highlight_style(
+                Style::default()
+                    .bg(theme::current().selection)
+                    .fg(Color::White),
+            );
+
+        self.area = area;
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+
+    /// Whether `position` falls inside the table as last drawn.
+    pub(crate) fn contains(&self, position: Position) -> bool {
+        self.area.contains(position)
+    }
+
+    /// Handle a left click: a header sorts by that column (flipping the direction when
+    /// it already is the sort column) and a row selects its process.
+    pub(crate) fn click(&mut self, position: Position) {
+        let inner = self.area.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            return;
+        }
+        if position.y == inner.y {
+            let columns = Layout::horizontal(COLUMN_WIDTHS).spacing(1).split(inner);
+            let sort = columns
+                .iter()
+                .position(|column| column.contains(position))
+                .and_then(column_sort);
+            if let Some(sort) = sort {
+                self.sort_by(sort);
+            }
+        } else {
+            let row = self.state.offset() + usize::from(position.y - inner.y - 1);
+            if row < self.rows.len() {
+                self.state.select(Some(row));
+            }
+        }
+    }
+
+    /// Move the selection for a mouse wheel notch.
+    pub(crate) fn scroll(&mut self, down: bool) {
+        self.move_selection(if down { SCROLL_ROWS } else { -SCROLL_ROWS });
+    }
+}
+
+/// Column titles, in display order.
+const COLUMNS: [&str; 10] = [
+    "PID", "USER", "CPU%", "RSS", "VIRT", "READ/s", "WRITE/s", "TIME", "STATE", "COMMAND",
+];
+
+const COLUMN_WIDTHS: [Constraint; 10] = [
+    Constraint::Length(8),
+    Constraint::Length(10),
+    Constraint::Length(7),
+    Constraint::Length(11),
+    Constraint::Length(11),
+    Constraint::Length(13),
+    Constraint::Length(13),
+    Constraint::Length(12),
+    Constraint::Length(10),
+    Constraint::Min(10),
+];
+
+/// Rows moved per mouse wheel notch.
+const SCROLL_ROWS: isize = 3;
+
+/// The sort order behind the column at `index`; USER, VIRT and STATE have none.
+fn column_sort(index: usize) -> Option<SortColumn> {
+    match COLUMNS[index] {
+        "PID" => Some(SortColumn::Pid),
+        "CPU%" => Some(SortColumn::Cpu),
+        "RSS" => Some(SortColumn::Memory),
+        "READ/s" | "WRITE/s" => Some(SortColumn::DiskIo),
+        "TIME" => Some(SortColumn::RunTime),
+        "COMMAND" => Some(SortColumn::Name),
+        _ => None,
+    }
+}
+
+/// Lay `processes` out as a forest, each child under its parent.
+///
+/// `processes` is expected to be sorted already; siblings keep that relative order. A
+/// process whose parent is not in the snapshot becomes a root. Descendants of collapsed
+/// PIDs are left out and their parent is marked with `+`.
+fn tree_rows(processes: &[ProcessInfo], collapsed: &HashSet<u32>) -> Vec<VisibleRow> {
+    let index_of: HashMap<u32, usize> = processes
+        .iter()
+        .enumerate()
+        .map(|(index, process)| (process.pid, index))
+        .collect();
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (index, process) in processes.iter().enumerate() {
+        match process
+            .parent
+            .filter(|parent| *parent != process.pid)
+            .and_then(|parent| index_of.get(&parent))
+        {
+            Some(&parent) => children.entry(parent).or_default().push(index),
+            None => roots.push(index),
+        }
+    }
+
+    let mut walk = Walk {
+        processes,
+        children: &children,
+        collapsed,
+        visited: vec![false; processes.len()],
+        rows: Vec::with_capacity(processes.len()),
+    };
+    for root in roots {
+        walk.node(root, "", None);
+    }
+    // Parent links that form a cycle leave processes unreachable from any root; list them
+    // at the top level rather than dropping them.
+    for index in 0..processes.len() {
+        if !walk.visited[index] {
+            walk.node(index, "", None);
+        }
+    }
+    walk.rows
+}
+
+struct Walk<'a> {
+    processes: &'a [ProcessInfo],
+    children: &'a HashMap<usize, Vec<usize>>,
+    collapsed: &'a HashSet<u32>,
+    visited: Vec<bool>,
+    rows: Vec<VisibleRow>,
+}
+
+impl Walk<'_> {
+    /// Emit `index` and its visible descendants. `last` is `None` for roots, otherwise
+    /// whether the node is the last of its siblings.
+    fn node(&mut self, index: usize, indent: &str, last: Option<bool>) {
+        if self.visited[index] {
+            return;
+        }
+        self.visited[index] = true;
+        let children = self.children.get(&index).map_or(&[][..], Vec::as_slice);
+        let is_collapsed = self.collapsed.contains(&self.processes[index].pid);
+        let branch = match last {
+            None => "",
+            Some(true) => "└─",
+            Some(false) => "├─",
+        };
+        let marker = match (children.is_empty(), is_collapsed) {
+            (true, _) => " ",
+            (false, true) => "+",
+            (false, false) => "-",
+        };
+        self.rows.push(VisibleRow {
+            index,
+            guide: format!("{indent}{branch}{marker} "),
+        });
+        if is_collapsed {
+            self.hide(index);
+            return;
+        }
+        let child_indent = match last {
+            None => indent.to_string(),
+            Some(true) => format!("{indent}   "),
+            Some(false) => format!("{indent}│  "),
+        };
+        for (position, &child) in children.iter().enumerate() {
+            self.node(child, &child_indent, Some(position + 1 == children.len()));
+        }
+    }
+
+    /// Mark the descendants of a collapsed `index` as seen, so the cycle fallback in
+    /// [`tree_rows`] does not list them.
+    fn hide(&mut self, index: usize) {
+        for &child in self.children.get(&index).map_or(&[][..], Vec::as_slice) {
+            if !self.visited[child] {
+                self.visited[child] = true;
+                self.hide(child);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, command: &str) -> ProcessInfo {
+        ProcessInfo {
+            command: command.to_string(),
+            ..ProcessInfo::example(pid, name)
+        }
+    }
+
+    #[test]
+    fn filter_matches_name_or_command_ignoring_case() {
+        let filter = ProcessFilter::new(Some("Nginx"), &[]);
+        assert!(filter.matches(&process(1, "nginx", "nginx: master")));
+        assert!(filter.matches(&process(2, "worker", "/usr/sbin/NGINX -g daemon")));
+        assert!(!filter.matches(&process(3, "sshd", "/usr/sbin/sshd")));
+    }
+
+    #[test]
+    fn filter_combines_text_and_pids() {
+        let filter = ProcessFilter::new(Some("sh"), &[10, 20]);
+        assert!(filter.matches(&process(10, "bash", "bash")));
+        assert!(!filter.matches(&process(30, "bash", "bash")));
+        assert!(!filter.matches(&process(20, "init", "/sbin/init")));
+        let everything = ProcessFilter::new(Some(""), &[]);
+        assert!(everything.matches(&process(1, "init", "/sbin/init")));
+        assert_eq!(everything.describe(), "");
+        assert_eq!(filter.describe(), " [filter: \"sh\", 2 pids]");
+    }
+
+    #[test]
+    fn keeps_the_busiest_matching_processes() {
+        let mut processes = vec![
+            process(1, "init", "/sbin/init"),
+            process(20, "bash", "bash"),
+            process(30, "bash", "bash -l"),
+            process(40, "sshd", "sshd"),
+        ];
+        for (process, cpu_usage) in processes.iter_mut().zip([0.1, 3.0, 40.0, 90.0]) {
+            process.cpu_usage = cpu_usage;
+        }
+        keep_busiest(
+            &mut processes,
+            &ProcessFilter::new(Some("bash"), &[]),
+            Some(1),
+        );
+        let pids: Vec<u32> = processes.iter().map(|process| process.pid).collect();
+        assert_eq!(pids, [30]);
+    }
+
+    fn with_cpu(pid: u32, cpu_usage: f32) -> ProcessInfo {
+        ProcessInfo {
+            cpu_usage,
+            ..process(pid, "p", "")
+        }
+    }
+
+    fn row_pids(table: &ProcessTable) -> Vec<u32> {
+        table
+            .rows
+            .iter()
+            .map(|row| table.processes[row.index].pid)
+            .collect()
+    }
+
+    #[test]
+    fn sorts_are_reversible_and_ties_go_by_pid() {
+        let mut table = ProcessTable::default();
+        table.set_processes(vec![with_cpu(3, 5.0), with_cpu(1, 5.0), with_cpu(2, 9.0)]);
+        assert_eq!(row_pids(&table), [2, 3, 1]);
+        table.reverse_sort();
+        assert_eq!(row_pids(&table), [1, 3, 2]);
+        table.sort_by(SortColumn::Pid);
+        assert_eq!(row_pids(&table), [1, 2, 3]);
+        table.sort_by(SortColumn::Pid);
+        assert_eq!(row_pids(&table), [3, 2, 1]);
+    }
+
+    #[test]
+    fn selection_follows_the_pid_across_refreshes() {
+        let mut table = ProcessTable::default();
+        table.set_processes(vec![with_cpu(1, 9.0), with_cpu(2, 5.0), with_cpu(3, 1.0)]);
+        table.select_next();
+        assert_eq!(table.selected().map(|process| process.pid), Some(2));
+        // Process 2 is now the busiest and moves to the top.
+        table.set_processes(vec![with_cpu(1, 9.0), with_cpu(2, 50.0), with_cpu(3, 1.0)]);
+        assert_eq!(row_pids(&table), [2, 1, 3]);
+        assert_eq!(table.selected().map(|process| process.pid), Some(2));
+        // Once it exits the selection stays on the same row.
+        table.set_processes(vec![with_cpu(1, 9.0), with_cpu(3, 1.0)]);
+        assert_eq!(table.selected().map(|process| process.pid), Some(1));
+    }
+
+    fn child(pid: u32, parent: u32) -> ProcessInfo {
+        ProcessInfo {
+            parent: Some(parent),
+            ..process(pid, "p", "")
+        }
+    }
+
+    /// Each row as its guide followed by its PID.
+    fn tree(processes: &[ProcessInfo], collapsed: &[u32]) -> Vec<String> {
+        let collapsed = collapsed.iter().copied().collect();
+        tree_rows(processes, &collapsed)
+            .iter()
+            .map(|row| format!("{}{}", row.guide, processes[row.index].pid))
+            .collect()
+    }
+
+    #[test]
+    fn tree_nests_children_and_orphans_become_roots() {
+        let processes = [
+            process(1, "init", ""),
+            child(2, 1),
+            child(3, 2),
+            child(4, 1),
+            child(5, 99),
+        ];
+        assert_eq!(
+            tree(&processes, &[]),
+            ["- 1", "├─- 2", "│  └─  3", "└─  4", "  5"]
+        );
+    }
+
+    #[test]
+    fn collapsed_parents_hide_their_descendants() {
+        let processes = [
+            process(1, "init", ""),
+            child(2, 1),
+            child(3, 2),
+            child(4, 1),
+        ];
+        assert_eq!(tree(&processes, &[2]), ["- 1", "├─+ 2", "└─  4"]);
+    }
+
+    #[test]
+    fn parent_loops_still_list_every_process_once() {
+        let processes = [child(1, 1), child(2, 3), child(3, 2)];
+        // 3 still points back at 2, which is drawn above it.
+        assert_eq!(tree(&processes, &[]), ["  1", "- 2", "└─- 3"]);
+    }
+}
@@ -0,0 +1,460 @@
+//! Recording samples to disk with the `record` subcommand and playing them back in the
+//! dashboard with `--replay`.
+//!
+//! A recording is NDJSON: a header line with the format version, then one line per
+//! sample holding the load average and the [`Sample`] itself. The rest of the host
+//! details only appear on the first line and whenever they change. Each line is flushed
+//! as it is written, so stopping the recorder with Ctrl-C loses at most the line in
+//! progress.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, bail, eyre},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+
+use crate::{
+    Event,
+    cli::RecordArgs,
+    format,
+    history::RETENTION,
+    host::HostInfo,
+    process,
+    sampler::{Sample, Sampler},
+};
+
+/// Bumped whenever a change to the sample structs would break reading older files.
+const VERSION: u32 = 1;
+
+/// Playback speeds cycled through with `f`.
+const SPEEDS: [u32; 5] = [1, 2, 4, 8, 16];
+
+/// How far `[` and `]` move through a recording.
+const JUMP: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    recording_version: u32,
+}
+
+/// One line of a recording as stored on disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct Line {
+    /// Left out while the machine's identity is unchanged since the last line that has it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    host: Option<HostInfo>,
+    load_average: [f64; 3],
+    sample: Sample,
+}
+
+/// One sample of a recording with the host details in effect when it was taken.
+#[derive(Debug, Clone)]
+struct Entry {
+    host: HostInfo,
+    sample: Sample,
+}
+
+/// Whether two host samples describe the same machine, ignoring what moves over time.
+fn same_machine(a: &HostInfo, b: &HostInfo) -> bool {
+    a.host_name == b.host_name
+        && a.os == b.os
+        && a.kernel == b.kernel
+        && a.boot_time == b.boot_time
+        && a.cpu_brand == b.cpu_brand
+        && a.physical_cores == b.physical_cores
+        && a.logical_cores == b.logical_cores
+}
+
+/// Writes the header and then one line per sample.
+struct Writer<W> {
+    out: W,
+    /// The host details last written out.
+    host: Option<HostInfo>,
+}
+
+impl<W: Write> Writer<W> {
+    fn new(mut out: W) -> io::Result<Self> {
+        serde_json::to_writer(
+            &mut out,
+            &Header {
+                recording_version: VERSION,
+            },
+        )?;
+        writeln!(out)?;
+        Ok(Self { out, host: None })
+    }
+
+    fn write(&mut self, host: &HostInfo, sample: Sample) -> io::Result<()> {
+        let changed = self
+            .host
+            .as_ref()
+            .is_none_or(|last| !same_machine(last, host));
+        if changed {
+            self.host = Some(host.clone());
+        }
+        let line = Line {
+            host: changed.then(|| host.clone()),
+            load_average: host.load_average,
+            sample,
+        };
+        serde_json::to_writer(&mut self.out, &line)?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
+
+/// Sample every `interval` and append to `args.file` until `args.count` samples are
+/// written, or until interrupted when no count is given.
+pub(crate) fn record(args: &RecordArgs, interval: Duration) -> Result<()> {
+    let path = &args.file;
+    let file =
+        File::create(path).wrap_err_with(|| format!("could not create {}", path.display()))?;
+    eprintln!(
+        "recording to {} every {} ms, press Ctrl-C to stop",
+        path.display(),
+        interval.as_millis()
+    );
+    write_entries(args, interval, BufWriter::new(file))
+        .wrap_err_with(|| format!("could not write {}", path.display()))
+}
+
+fn write_entries(args: &RecordArgs, interval: Duration, out: impl Write) -> io::Result<()> {
+    let mut writer = Writer::new(out)?;
+    let filter = args.processes.filter();
+    let mut sampler = Sampler::new();
+    let mut written = 0;
+    while args.count.is_none_or(|count| written < count.get()) {
+        thread::sleep(interval.max(MINIMUM_CPU_UPDATE_INTERVAL));
+        let mut sample = sampler.sample();
+        process::keep_busiest(&mut sample.processes, &filter, Some(args.top));
+        writer.write(&sampler.host(), sample)?;
+        written += 1;
+    }
+    Ok(())
+}
+
+/// Every sample of a recording, oldest first.
+#[derive(Debug)]
+pub(crate) struct Recording {
+    entries: Vec<Entry>,
+}
+
+impl Recording {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).wrap_err_with(|| format!("could not open {}", path.display()))?;
+        Self::read(BufReader::new(file))
+            .wrap_err_with(|| format!("could not replay {}", path.display()))
+    }
+
+    fn read(reader: impl BufRead) -> Result<Self> {
+        let mut lines = reader.lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let header: Header = serde_json::from_str(&header)
+            .map_err(|_| eyre!("not a recording made by the record subcommand"))?;
+        if header.recording_version != VERSION {
+            bail!(
+                "recording format version {} is not supported, expected {VERSION}",
+                header.recording_version
+            );
+        }
+        let mut entries: Vec<Entry> = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let line: Line = match serde_json::from_str(&line?) {
+                Ok(line) => line,
+                // The recorder was stopped in the middle of writing this line.
+                Err(err) if err.is_eof() => break,
+                Err(err) => return Err(err).wrap_err_with(|| format!("line {}", index + 2)),
+            };
+            let mut host = match (line.host, entries.last()) {
+                (Some(host), _) => host,
+                // Carry the host forward, advancing its uptime by the time between samples.
+                (None, Some(previous)) => {
+                    let mut host = previous.host.clone();
+                    host.uptime += Duration::from_millis(
+                        line.sample
+                            .timestamp_ms
+                            .saturating_sub(previous.sample.timestamp_ms),
+                    );
+                    host
+                }
+                (None, None) => bail!("line {}: the first sample has no host details", index + 2),
+            };
+            host.load_average = line.load_average;
+            entries.push(Entry {
+                host,
+                sample: line.sample,
+            });
+        }
+        if entries.is_empty() {
+            bail!("the recording holds no samples");
+        }
+        Ok(Self { entries })
+    }
+}
+
+/// Position and pace of playback through a [`Recording`].
+#[derive(Debug)]
+pub(crate) struct Player {
+    entries: Vec<Entry>,
+    position: usize,
+    playing: bool,
+    speed: usize,
+    /// Stands in for the time of the first entry; later entries are offset from it.
+    origin: Instant,
+    /// When the entry at `position` became due, to pace playback.
+    shown_at: Instant,
+}
+
+impl Player {
+    pub(crate) fn new(recording: Recording) -> Self {
+        let now = Instant::now();
+        Self {
+            entries: recording.entries,
+            position: 0,
+            playing: true,
+            speed: 0,
+            origin: now,
+            shown_at: now,
+        }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    fn timestamp_ms(&self, index: usize) -> u64 {
+        self.entries[index].sample.timestamp_ms
+    }
+
+    /// The instant the history charts should record the entry at `index` at.
+    pub(crate) fn instant(&self, index: usize) -> Instant {
+        let offset = self
+            .timestamp_ms(index)
+            .saturating_sub(self.timestamp_ms(0));
+        self.origin + Duration::from_millis(offset)
+    }
+
+    /// The events the live sampler would have sent for the entry at `index`. Entries that
+    /// are only replayed to rebuild the history leave out the process list.
+    pub(crate) fn events(&self, index: usize, with_processes: bool) -> Vec<Event> {
+        let entry = &self.entries[index];
+        let sample = if with_processes {
+            entry.sample.clone()
+        } else {
+            entry.sample.without_processes()
+        };
+        let mut events = vec![Event::Host(entry.host.clone())];
+        events.extend(
+            sample
+                .into_events()
+                .into_iter()
+                .filter(|event| with_processes || !matches!(event, Event::Processes(_))),
+        );
+        events
+    }
+
+    /// First entry whose samples are still within the history's retention when the entry
+    /// at `index` is shown.
+    pub(crate) fn history_start(&self, index: usize) -> usize {
+        let oldest = self
+            .timestamp_ms(index)
+            .saturating_sub(RETENTION.as_millis() as u64);
+        self.entries[..index].partition_point(|entry| entry.sample.timestamp_ms < oldest)
+    }
+
+    /// Move to the next entry if playback has reached it, at the current speed.
+    pub(crate) fn advance(&mut self) -> Option<usize> {
+        let next = self.position + 1;
+        if !self.playing {
+            return None;
+        }
+        if next >= self.entries.len() {
+            self.playing = false;
+            return None;
+        }
+        let gap = self
+            .timestamp_ms(next)
+            .saturating_sub(self.timestamp_ms(self.position));
+        let gap = Duration::from_millis(gap) / SPEEDS[self.speed];
+        if self.shown_at.elapsed() < gap {
+            return None;
+        }
+        self.shown_at += gap;
+        self.position = next;
+        Some(next)
+    }
+
+    /// Move `delta` entries back or forth and pause there.
+    pub(crate) fn step(&mut self, delta: isize) -> usize {
+        self.playing = false;
+        self.seek(self.position.saturating_add_signed(delta))
+    }
+
+    /// Move a minute back or forth in recorded time.
+    pub(crate) fn jump(&mut self, forward: bool) -> usize {
+        let now = self.timestamp_ms(self.position);
+        let jump = JUMP.as_millis() as u64;
+        let target = if forward {
+            now.saturating_add(jump)
+        } else {
+            now.saturating_sub(jump)
+        };
+        let index = self
+            .entries
+            .partition_point(|entry| entry.sample.timestamp_ms < target);
+        self.seek(index)
+    }
+
+    fn seek(&mut self, index: usize) -> usize {
+        self.position = index.min(self.entries.len() - 1);
+        self.shown_at = Instant::now();
+        self.position
+    }
+
+    pub(crate) fn toggle_playing(&mut self) {
+        self.playing = !self.playing;
+        if self.playing && self.position + 1 == self.entries.len() {
+            // Play again from the start rather than sitting at the end.
+            self.seek(0);
+        }
+        self.shown_at = Instant::now();
+    }
+
+    pub(crate) fn faster(&mut self) {
+        self.speed = (self.speed + 1) % SPEEDS.len();
+    }
+
+    /// One-line summary for the status bar.
+    pub(crate) fn status(&self) -> String {
+        format!(
+            "replay {} ({}/{}) {} {}x   space play/pause  ,/. step  [/] ±1 min  f speed",
+            format::timestamp(self.timestamp_ms(self.position) / 1000),
+            self.position + 1,
+            self.entries.len(),
+            if self.playing { "▶" } else { "⏸" },
+            SPEEDS[self.speed],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp_ms: u64) -> Entry {
+        Entry {
+            host: HostInfo::example(),
+            sample: Sample {
+                timestamp_ms,
+                ..Sample::example()
+            },
+        }
+    }
+
+    fn file(entries: &[Entry]) -> String {
+        let mut writer = Writer::new(Vec::new()).unwrap();
+        for entry in entries {
+            writer.write(&entry.host, entry.sample.clone()).unwrap();
+        }
+        String::from_utf8(writer.out).unwrap()
+    }
+
+    fn player(timestamps: &[u64]) -> Player {
+        let entries: Vec<Entry> = timestamps.iter().map(|&ms| entry(ms)).collect();
+        Player::new(Recording::read(file(&entries).as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn entries_round_trip_and_replay_their_recorded_uptime() {
+        let mut second = entry(2500);
+        second.host.load_average = [1.5, 1.0, 0.5];
+        let text = file(&[entry(1000), second]);
+        assert_eq!(text.matches("host_name").count(), 1);
+        let recording = Recording::read(text.as_bytes()).unwrap();
+        assert_eq!(recording.entries.len(), 2);
+        assert_eq!(recording.entries[1].sample.timestamp_ms, 2500);
+        let host = &recording.entries[0].host;
+        assert!(host.sampled_at.is_none());
+        assert_eq!(host.current_uptime(), Duration::from_secs(90));
+        let host = &recording.entries[1].host;
+        assert_eq!(host.host_name, "box");
+        assert_eq!(host.load_average, [1.5, 1.0, 0.5]);
+        assert_eq!(host.current_uptime(), Duration::from_millis(91_500));
+    }
+
+    #[test]
+    fn host_details_are_written_again_when_they_change() {
+        let mut rebooted = entry(2000);
+        rebooted.host.kernel = "6.2".to_string();
+        let text = file(&[entry(1000), rebooted, entry(3000)]);
+        assert_eq!(text.matches("host_name").count(), 3);
+        let recording = Recording::read(text.as_bytes()).unwrap();
+        assert_eq!(recording.entries[1].host.kernel, "6.2");
+        assert_eq!(recording.entries[2].host.kernel, "6.1");
+    }
+
+    #[test]
+    fn a_line_cut_short_ends_the_recording() {
+        let mut text = file(&[entry(1000), entry(2000)]);
+        text.push_str("{\"host\":{\"host_name\":");
+        let recording = Recording::read(text.as_bytes()).unwrap();
+        assert_eq!(recording.entries.len(), 2);
+    }
+
+    #[test]
+    fn other_files_are_rejected() {
+        let error = |text: &str| format!("{:#}", Recording::read(text.as_bytes()).unwrap_err());
+        assert!(error("{\"refresh_ms\":5}").contains("not a recording"));
+        assert!(error("{\"recording_version\":99}\n").contains("version 99"));
+        assert!(error(&file(&[])).contains("no samples"));
+        assert!(error(&format!("{}[1, 2]\n", file(&[entry(0)]))).contains("line 3"));
+        let headless = file(&[entry(0)]).replacen("\"host\":", "\"old_host\":", 1);
+        assert!(error(&headless).contains("no host details"));
+    }
+
+    #[test]
+    fn steps_and_jumps_stay_in_bounds() {
+        let mut player = player(&[0, 30_000, 60_000, 90_000, 200_000]);
+        assert_eq!(player.step(-1), 0);
+        assert_eq!(player.jump(true), 2);
+        assert_eq!(player.jump(true), 4);
+        assert_eq!(player.step(1), 4);
+        assert_eq!(player.jump(false), 4);
+        assert_eq!(player.step(-2), 2);
+        assert_eq!(player.jump(false), 0);
+        assert_eq!(
+            player.instant(3).duration_since(player.instant(0)),
+            Duration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn history_is_rebuilt_from_the_retained_hour() {
+        let hour = RETENTION.as_millis() as u64;
+        let player = player(&[0, 1000, hour, hour + 1000, hour + 2000]);
+        assert_eq!(player.history_start(4), 2);
+        assert_eq!(player.history_start(1), 0);
+        assert!(
+            player
+                .events(1, false)
+                .iter()
+                .all(|event| !matches!(event, Event::Processes(_)))
+        );
+        assert!(
+            player
+                .events(1, true)
+                .iter()
+                .any(|event| matches!(event, Event::Processes(_)))
+        );
+    }
+}
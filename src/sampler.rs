@@ -0,0 +1,154 @@
+//! Collects every panel's data from the live system in one place, so the dashboard and
+//! the headless subcommands report the same numbers.
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{Components, Disks, Networks, System, Users};
+
+use crate::{
+    Event,
+    cpu::CpuSnapshot,
+    disk::DiskInfo,
+    diskio::{DeviceIo, DiskStatsSampler},
+    host::HostInfo,
+    memory::MemorySnapshot,
+    network::InterfaceInfo,
+    process::ProcessInfo,
+    sensors::SensorInfo,
+};
+
+/// Everything one refresh produces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Sample {
+    /// Milliseconds since the Unix epoch at which the sample was taken.
+    pub timestamp_ms: u64,
+    pub cpu: CpuSnapshot,
+    pub memory: MemorySnapshot,
+    pub processes: Vec<ProcessInfo>,
+    pub networks: Vec<InterfaceInfo>,
+    pub disks: Vec<DiskInfo>,
+    /// Block device activity; `None` where /proc/diskstats does not exist.
+    pub disk_io: Option<Vec<DeviceIo>>,
+    pub components: Vec<SensorInfo>,
+}
+
+impl Sample {
+    /// A copy with an empty process list, for when only the other panels are needed.
+    pub(crate) fn without_processes(&self) -> Sample {
+        Sample {
+            timestamp_ms: self.timestamp_ms,
+            cpu: self.cpu.clone(),
+            memory: self.memory.clone(),
+            processes: Vec::new(),
+            networks: self.networks.clone(),
+            disks: self.disks.clone(),
+            disk_io: self.disk_io.clone(),
+            components: self.components.clone(),
+        }
+    }
+
+    /// The events the dashboard consumes, one per panel.
+    pub(crate) fn into_events(self) -> Vec<Event> {
+        let mut events = vec![
+            Event::Memory(self.memory),
+            Event::Cpu(self.cpu),
+            Event::Processes(self.processes),
+            Event::Network(self.networks),
+            Event::Disks(self.disks),
+        ];
+        if let Some(devices) = self.disk_io {
+            events.push(Event::DiskIo(devices));
+        }
+        events.push(Event::Sensors(self.components));
+        events
+    }
+}
+
+#[cfg(test)]
+impl Sample {
+    /// A sample taken 2024-03-01 09:30:00 UTC holding a single busy `bash`, for tests to
+    /// adjust.
+    pub(crate) fn example() -> Self {
+        Self {
+            timestamp_ms: 1_709_285_400_000,
+            cpu: CpuSnapshot {
+                global_usage: 12.5,
+                cores: Vec::new(),
+            },
+            memory: MemorySnapshot {
+                total: 2048,
+                used: 1024,
+                available: 1024,
+                free: 512,
+                swap_total: 0,
+                swap_used: 0,
+                details: None,
+            },
+            processes: vec![ProcessInfo {
+                parent: Some(1),
+                cpu_usage: 40.0,
+                memory: 1024,
+                virtual_memory: 4096,
+                run_time: 61,
+                ..ProcessInfo::example(30, "bash")
+            }],
+            networks: Vec::new(),
+            disks: Vec::new(),
+            disk_io: None,
+            components: Vec::new(),
+        }
+    }
+}
+
+/// The `sysinfo` handles and counters that rates are computed against between samples.
+pub(crate) struct Sampler {
+    sys: System,
+    users: Users,
+    networks: Networks,
+    disks: Disks,
+    disk_stats: DiskStatsSampler,
+    components: Components,
+    last_refresh: Instant,
+}
+
+impl Sampler {
+    pub(crate) fn new() -> Self {
+        Self {
+            sys: System::new_all(),
+            users: Users::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            disk_stats: DiskStatsSampler::new(),
+            components: Components::new_with_refreshed_list(),
+            last_refresh: Instant::now(),
+        }
+    }
+
+    /// Refresh everything and describe it. CPU usage and the rates cover the time since
+    /// the previous call (or since [`Sampler::new`]).
+    pub(crate) fn sample(&mut self) -> Sample {
+        self.sys.refresh_all();
+        self.networks.refresh(true);
+        self.disks.refresh(true);
+        self.components.refresh(true);
+        let elapsed = self.last_refresh.elapsed();
+        self.last_refresh = Instant::now();
+        Sample {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |now| now.as_millis() as u64),
+            cpu: CpuSnapshot::sample(&self.sys),
+            memory: MemorySnapshot::sample(&self.sys),
+            processes: ProcessInfo::snapshot(&self.sys, &self.users, elapsed),
+            networks: InterfaceInfo::snapshot(&self.networks, elapsed),
+            disks: DiskInfo::snapshot(&self.disks),
+            disk_io: self.disk_stats.sample(),
+            components: SensorInfo::snapshot(&self.components),
+        }
+    }
+
+    pub(crate) fn host(&self) -> HostInfo {
+        HostInfo::sample(&self.sys)
+    }
+}
@@ -0,0 +1,322 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+};
+use serde::{Deserialize, Serialize};
+use sysinfo::Components;
+
+use crate::{history::HistoryStore, theme};
+
+/// Where the Linux kernel exposes hardware monitoring chips.
+pub(crate) const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// Width of the temperature sparkline on each sensor row.
+const TREND_WIDTH: u16 = 20;
+
+/// One temperature sensor, in degrees Celsius.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SensorInfo {
+    pub label: String,
+    pub temperature: Option<f32>,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+impl SensorInfo {
+    /// Read every sensor `sysinfo` knows about, falling back to walking the hwmon tree
+    /// directly when it reports none, sorted by label. Labels are made unique, since they
+    /// key the history and the exporter's series.
+    pub(crate) fn snapshot(components: &Components) -> Vec<SensorInfo> {
+        let mut sensors: Vec<SensorInfo> = components
+            .list()
+            .iter()
+            .map(|component| SensorInfo {
+                label: component.label().to_string(),
+                temperature: component.temperature(),
+                max: component.max(),
+                critical: component.critical(),
+            })
+            .collect();
+        if sensors.is_empty() {
+            sensors = read_hwmon(Path::new(HWMON_ROOT));
+        }
+        sensors.sort_by(|a, b| a.label.cmp(&b.label));
+        number_repeated_labels(&mut sensors);
+        sensors
+    }
+}
+
+/// Append `#1`, `#2`, ... to labels shared by several sensors, such as the
+/// "nvme Composite" of each NVMe drive, in the order the sensors were listed.
+fn number_repeated_labels(sensors: &mut [SensorInfo]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for sensor in sensors.iter() {
+        *counts.entry(sensor.label.clone()).or_default() += 1;
+    }
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for sensor in sensors {
+        if counts[&sensor.label] > 1 {
+            let number = seen.entry(sensor.label.clone()).or_default();
+            *number += 1;
+            sensor.label = format!("{} #{number}", sensor.label);
+        }
+    }
+}
+
+/// Collect the `tempN_*` sensors of every `hwmonX` chip under `root`.
+///
+/// Each chip directory holds a `name` file and, per sensor, `tempN_input` plus optional
+/// `tempN_label`, `tempN_max` and `tempN_crit`, all in millidegrees Celsius. Sensors
+/// without a label are named after their chip. A missing or unreadable `root` yields no
+/// sensors.
+pub(crate) fn read_hwmon(root: &Path) -> Vec<SensorInfo> {
+    let Ok(chips) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut chips: Vec<_> = chips
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    chips.sort();
+
+    let mut sensors = Vec::new();
+    for chip in chips {
+        let chip_name = read_trimmed(&chip.join("name")).unwrap_or_else(|| {
+            chip.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        let Ok(files) = fs::read_dir(&chip) else {
+            continue;
+        };
+        let mut inputs: Vec<String> = files
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let file = entry.file_name().to_string_lossy().into_owned();
+                let prefix = file.strip_suffix("_input")?;
+                prefix.starts_with("temp").then(|| prefix.to_string())
+            })
+            .collect();
+        inputs.sort();
+        for prefix in inputs {
+            let millidegrees = |suffix: &str| {
+                read_trimmed(&chip.join(format!("{prefix}_{suffix}")))
+                    .and_then(|value| value.parse::<f32>().ok())
+                    .map(|value| value / 1000.0)
+            };
+            let label = read_trimmed(&chip.join(format!("{prefix}_label")))
+                .map(|label| format!("{chip_name} {label}"))
+                .unwrap_or_else(|| format!("{chip_name} {prefix}"));
+            sensors.push(SensorInfo {
+                label,
+                temperature: millidegrees("input"),
+                max: millidegrees("max"),
+                critical: millidegrees("crit"),
+            });
+        }
+    }
+    sensors
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+/// The theme's high color at or above the critical point, medium within 15°C of it, low
+/// otherwise. Sensors that report no critical point are judged against 90°C.
+pub(crate) fn temperature_color(temperature: f32, critical: Option<f32>) -> Color {
+    let critical = critical.filter(|c| *c > 0.0).unwrap_or(90.0);
+    let theme = theme::current();
+    if temperature >= critical {
+        theme.high
+    } else if temperature >= critical - 15.0 {
+        theme.medium
+    } else {
+        theme.low
+    }
+}
+
+/// Temperature table with per-sensor history, or a notice when there are no sensors.
+#[derive(Debug, Default)]
+pub(crate) struct SensorPanel {
+    latest: Vec<SensorInfo>,
+}
+
+impl SensorPanel {
+    /// Store the snapshot for display and append temperatures to `history`.
+    pub(crate) fn update(&mut self, sensors: Vec<SensorInfo>, history: &mut HistoryStore) {
+        let now = history.now();
+        for sensor in &sensors {
+            if let Some(temperature) = sensor.temperature {
+                history.record(format!("temp.{}", sensor.label), now, temperature as f64);
+            }
+        }
+        self.latest = sensors;
+    }
+
+    pub(crate) fn render(&self, history: &HistoryStore, area: Rect, frame: &mut Frame) {
+        let block = Block::new().title("sensors").borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if self.latest.is_empty() {
+            let notice = Paragraph::new(
+                "no temperature sensors found (virtual machines usually expose none)",
+            )
+            .style(Style::default().fg(Color::DarkGray))
+            .wrap(Wrap { trim: true });
+            frame.render_widget(notice, inner);
+            return;
+        }
+
+        let [table_area, trend_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(TREND_WIDTH + 1)])
+                .areas(inner);
+        let celsius = |value: Option<f32>| value.map_or("-".to_string(), |v| format!("{v:.1}°C"));
+        let header = Row::new(["SENSOR", "TEMP", "MAX", "CRIT"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.latest.iter().map(|sensor| {
+            let color = sensor
+                .temperature
+                .map_or(Color::DarkGray, |t| temperature_color(t, sensor.critical));
+            Row::new([
+                sensor.label.clone(),
+                celsius(sensor.temperature),
+                celsius(sensor.max),
+                celsius(sensor.critical),
+            ])
+            .style(Style::default().fg(color))
+        });
+        let widths = [
+            Constraint::Min(12),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ];
+        frame.render_widget(Table::new(rows, widths).header(header), table_area);
+
+        for (row, sensor) in self.latest.iter().enumerate() {
+            let y = trend_area.y + 1 + row as u16;
+            if y >= trend_area.bottom() {
+                break;
+            }
+            let line = Rect {
+                x: trend_area.x + 1,
+                y,
+                width: TREND_WIDTH.min(trend_area.width.saturating_sub(1)),
+                height: 1,
+            };
+            let max = sensor.critical.filter(|c| *c > 0.0).unwrap_or(100.0) as f64;
+            let color = sensor.temperature.map_or(theme::current().high, |t| {
+                temperature_color(t, sensor.critical)
+            });
+            let sparkline = history
+                .sparkline(&format!("temp.{}", sensor.label), max, TREND_WIDTH)
+                .style(Style::default().fg(color));
+            frame.render_widget(sparkline, line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn reads_labelled_and_unlabelled_sensors() {
+        let root = tempfile::tempdir().unwrap();
+        write(root.path(), "hwmon0/name", "coretemp\n");
+        write(root.path(), "hwmon0/temp1_input", "45000\n");
+        write(root.path(), "hwmon0/temp1_label", "Package id 0\n");
+        write(root.path(), "hwmon0/temp1_max", "80000\n");
+        write(root.path(), "hwmon0/temp1_crit", "100000\n");
+        write(root.path(), "hwmon1/name", "nvme\n");
+        write(root.path(), "hwmon1/temp1_input", "38850\n");
+        // Non-temperature inputs are ignored.
+        write(root.path(), "hwmon1/fan1_input", "1200\n");
+
+        let sensors = read_hwmon(root.path());
+
+        assert_eq!(
+            sensors,
+            vec![
+                SensorInfo {
+                    label: "coretemp Package id 0".to_string(),
+                    temperature: Some(45.0),
+                    max: Some(80.0),
+                    critical: Some(100.0),
+                },
+                SensorInfo {
+                    label: "nvme temp1".to_string(),
+                    temperature: Some(38.85),
+                    max: None,
+                    critical: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_tree_has_no_sensors() {
+        let root = tempfile::tempdir().unwrap();
+        assert!(read_hwmon(root.path()).is_empty());
+        assert!(read_hwmon(&root.path().join("does-not-exist")).is_empty());
+    }
+
+    #[test]
+    fn unparsable_readings_are_left_empty() {
+        let root = tempfile::tempdir().unwrap();
+        write(root.path(), "hwmon0/name", "acpitz\n");
+        write(root.path(), "hwmon0/temp1_input", "garbage\n");
+
+        let sensors = read_hwmon(root.path());
+
+        assert_eq!(sensors.len(), 1);
+        assert_eq!(sensors[0].temperature, None);
+    }
+
+    #[test]
+    fn repeated_labels_are_numbered() {
+        let sensor = |label: &str| SensorInfo {
+            label: label.to_string(),
+            temperature: None,
+            max: None,
+            critical: None,
+        };
+        let mut sensors = [
+            sensor("coretemp Package id 0"),
+            sensor("nvme Composite"),
+            sensor("nvme Composite"),
+        ];
+        number_repeated_labels(&mut sensors);
+        let labels: Vec<&str> = sensors.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "coretemp Package id 0",
+                "nvme Composite #1",
+                "nvme Composite #2"
+            ]
+        );
+    }
+
+    #[test]
+    fn colors_follow_the_critical_point() {
+        assert_eq!(temperature_color(50.0, Some(100.0)), Color::Green);
+        assert_eq!(temperature_color(85.0, Some(100.0)), Color::Yellow);
+        assert_eq!(temperature_color(100.0, Some(100.0)), Color::Red);
+        assert_eq!(temperature_color(80.0, None), Color::Yellow);
+        assert_eq!(temperature_color(60.0, Some(0.0)), Color::Green);
+    }
+}
@@ -0,0 +1,180 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListState, Paragraph},
+};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+/// Signals offered in the signal menu, in display order.
+pub(crate) const SIGNALS: [(&str, Signal); 9] = [
+    ("SIGTERM", Signal::Term),
+    ("SIGKILL", Signal::Kill),
+    ("SIGINT", Signal::Interrupt),
+    ("SIGHUP", Signal::Hangup),
+    ("SIGSTOP", Signal::Stop),
+    ("SIGCONT", Signal::Continue),
+    ("SIGQUIT", Signal::Quit),
+    ("SIGUSR1", Signal::User1),
+    ("SIGUSR2", Signal::User2),
+];
+
+/// Send `signal` to the process with the given PID, provided it is still the one that
+/// started at `start_time` rather than a newer process that reused the PID.
+///
+/// Returns a message suitable for the status line on failure, e.g. when the process has
+/// exited or belongs to another user.
+pub(crate) fn send_signal(pid: u32, start_time: u64, signal: Signal) -> Result<(), String> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    let process = sys
+        .process(pid)
+        .ok_or_else(|| format!("process {pid} no longer exists"))?;
+    if process.start_time() != start_time {
+        return Err(format!("process {pid} has been replaced"));
+    }
+    match process.kill_with(signal) {
+        Some(true) => Ok(()),
+        Some(false) => Err(format!(
+            "failed to send {signal} to {pid}: {}",
+            std::io::Error::last_os_error()
+        )),
+        None => Err(format!("{signal} is not supported on this platform")),
+    }
+}
+
+/// Modal popup state for the htop-style "send signal" flow: pick a signal, then confirm.
+#[derive(Debug)]
+pub(crate) enum SignalPopup {
+    Menu {
+        pid: u32,
+        /// Tells the chosen process apart from a later one given the same PID.
+        start_time: u64,
+        name: String,
+        list: ListState,
+    },
+    Confirm {
+        pid: u32,
+        start_time: u64,
+        name: String,
+        signal: usize,
+    },
+}
+
+impl SignalPopup {
+    pub(crate) fn new(pid: u32, start_time: u64, name: String) -> Self {
+        SignalPopup::Menu {
+            pid,
+            start_time,
+            name,
+            list: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if let SignalPopup::Menu { list, .. } = self {
+            let next = list
+                .selected()
+                .map_or(0, |i| (i + 1).min(SIGNALS.len() - 1));
+            list.select(Some(next));
+        }
+    }
+
+    pub(crate) fn select_previous(&mut self) {
+        if let SignalPopup::Menu { list, .. } = self {
+            list.select(Some(list.selected().map_or(0, |i| i.saturating_sub(1))));
+        }
+    }
+
+    /// Move from the menu to the confirmation step for the highlighted signal.
+    pub(crate) fn choose(&mut self) {
+        if let SignalPopup::Menu {
+            pid,
+            start_time,
+            name,
+            list,
+        } = self
+        {
+            *self = SignalPopup::Confirm {
+                pid: *pid,
+                start_time: *start_time,
+                name: std::mem::take(name),
+                signal: list.selected().unwrap_or(0),
+            };
+        }
+    }
+
+    pub(crate) fn render(&mut self, frame: &mut Frame) {
+        let area = centered(frame.area(), 40, SIGNALS.len() as u16 + 2);
+        frame.render_widget(Clear, area);
+        match self {
+            SignalPopup::Menu {
+                pid, name, list, ..
+            } => {
+                let block = Block::new()
+                    .title(format!("send signal to {pid} ({name})"))
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Yellow));
+                let items = SIGNALS.iter().map(|(label, _)| *label);
+                let menu = List::new(items)
+                    .block(block)
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+                frame.render_stateful_widget(menu, area, list);
+            }
+            SignalPopup::Confirm {
+                pid, name, signal, ..
+            } => {
+                let block = Block::new()
+                    .title("confirm")
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Yellow));
+                let text = vec![
+                    Line::from(format!("Send {} to {pid} ({name})?", SIGNALS[*signal].0)),
+                    Line::from(""),
+                    Line::from("[y] yes   [n] no"),
+                ];
+                frame.render_widget(Paragraph::new(text).block(block), area);
+            }
+        }
+    }
+}
+
+/// A `width` x `height` rectangle centered in `area`, clamped to fit.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn own_start_time() -> u64 {
+        let pid = Pid::from_u32(std::process::id());
+        let mut sys = System::new();
+        sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        sys.process(pid).unwrap().start_time()
+    }
+
+    #[test]
+    fn a_reused_pid_is_not_signalled() {
+        let pid = std::process::id();
+        let start_time = own_start_time();
+        assert_eq!(
+            send_signal(pid, start_time + 1, Signal::Continue),
+            Err(format!("process {pid} has been replaced"))
+        );
+        assert_eq!(send_signal(pid, start_time, Signal::Continue), Ok(()));
+    }
+}
@@ -0,0 +1,84 @@
+//! The `snapshot` subcommand: take one or more samples and print them as text, JSON or
+//! NDJSON without taking over the terminal.
+
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use color_eyre::Result;
+use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+
+use crate::{
+    batch::{self, DEFAULT_COLUMNS},
+    cli::{SnapshotArgs, SnapshotFormat},
+    process,
+    sampler::Sampler,
+};
+
+/// Processes listed by the text format when `--top` is not given.
+const TEXT_TOP: usize = 10;
+
+/// Print `args.count` samples taken `interval` apart. The first one also waits an
+/// interval, since CPU usage is measured between refreshes.
+pub(crate) fn run(args: &SnapshotArgs, interval: Duration) -> Result<()> {
+    batch::ignore_broken_pipe(write_samples(args, interval, &mut io::stdout().lock()))
+}
+
+fn write_samples(args: &SnapshotArgs, interval: Duration, out: &mut impl Write) -> io::Result<()> {
+    let filter = args.processes.filter();
+    let top = match (args.top, args.format) {
+        (Some(top), _) => Some(top),
+        (None, SnapshotFormat::Text) => Some(TEXT_TOP),
+        (None, _) => None,
+    };
+    let mut sampler = Sampler::new();
+    let mut samples = Vec::new();
+    for index in 0..args.count.get() {
+        thread::sleep(interval.max(MINIMUM_CPU_UPDATE_INTERVAL));
+        let mut sample = sampler.sample();
+        process::keep_busiest(&mut sample.processes, &filter, top);
+        match args.format {
+            SnapshotFormat::Text => {
+                if index > 0 {
+                    writeln!(out)?;
+                }
+                write!(
+                    out,
+                    "{}",
+                    batch::report(&sampler.host(), &sample, DEFAULT_COLUMNS)
+                )?;
+            }
+            SnapshotFormat::Ndjson => {
+                serde_json::to_writer(&mut *out, &sample)?;
+                writeln!(out)?;
+            }
+            SnapshotFormat::Json => samples.push(sample),
+        }
+        out.flush()?;
+    }
+    if args.format == SnapshotFormat::Json {
+        match samples.as_slice() {
+            [sample] => serde_json::to_writer_pretty(&mut *out, sample)?,
+            samples => serde_json::to_writer_pretty(&mut *out, samples)?,
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sampler::Sample;
+
+    #[test]
+    fn json_uses_the_field_names() {
+        let json = serde_json::to_value(Sample::example()).unwrap();
+        assert_eq!(json["timestamp_ms"], 1_709_285_400_000u64);
+        assert_eq!(json["memory"]["used"], 1024);
+        assert_eq!(json["processes"][0]["name"], "bash");
+        assert!(json["disk_io"].is_null());
+        assert!(json["components"].as_array().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use clap::ValueEnum;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Tabs,
+};
+
+/// The screens the app can show, in tab bar order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub(crate) enum Tab {
+    #[default]
+    Overview,
+    Processes,
+    Network,
+    Disks,
+    Sensors,
+}
+
+impl Tab {
+    pub(crate) const ALL: [Tab; 5] = [
+        Tab::Overview,
+        Tab::Processes,
+        Tab::Network,
+        Tab::Disks,
+        Tab::Sensors,
+    ];
+
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Processes => "Processes",
+            Tab::Network => "Network",
+            Tab::Disks => "Disks",
+            Tab::Sensors => "Sensors",
+        }
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|tab| *tab == self).unwrap_or(0)
+    }
+
+    pub(crate) fn next(self) -> Self {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    pub(crate) fn previous(self) -> Self {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+
+    /// The tab bound to number key `digit`, counting from 1.
+    pub(crate) fn from_digit(digit: char) -> Option<Self> {
+        let index = digit.to_digit(10)?.checked_sub(1)?;
+        Tab::ALL.get(index as usize).copied()
+    }
+
+    /// Whether the process table is drawn on this tab.
+    pub(crate) fn shows_processes(self) -> bool {
+        matches!(self, Tab::Overview | Tab::Processes)
+    }
+}
+
+/// The row of tab titles, which remembers where it was drawn so clicks can be mapped
+/// back to a tab.
+#[derive(Debug, Default)]
+pub(crate) struct TabBar {
+    area: Rect,
+}
+
+impl TabBar {
+    pub(crate) fn render(&mut self, selected: Tab, area: Rect, frame: &mut Frame) {
+        let titles = Tab::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{} {}", index + 1, tab.title()));
+        let tabs = Tabs::new(titles)
+            .select(selected.index())
+            .style(Style::default().fg(Color::DarkGray))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            );
+        self.area = area;
+        frame.render_widget(tabs, area);
+    }
+
+    /// The tab drawn at `(column, row)`, if any. Mirrors the `Tabs` widget's layout: each
+    /// title is padded by one space on both sides and followed by a one-column divider.
+    pub(crate) fn tab_at(&self, column: u16, row: u16) -> Option<Tab> {
+        if row != self.area.y || column < self.area.x {
+            return None;
+        }
+        let mut x = self.area.x;
+        for (index, tab) in Tab::ALL.iter().enumerate() {
+            let width = format!("{} {}", index + 1, tab.title()).len() as u16 + 2;
+            if column < x + width {
+                return Some(*tab);
+            }
+            x += width + 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigation_wraps_around() {
+        assert_eq!(Tab::Sensors.next(), Tab::Overview);
+        assert_eq!(Tab::Overview.previous(), Tab::Sensors);
+        assert_eq!(Tab::Network.next(), Tab::Disks);
+    }
+
+    #[test]
+    fn number_keys_count_from_one() {
+        assert_eq!(Tab::from_digit('1'), Some(Tab::Overview));
+        assert_eq!(Tab::from_digit('5'), Some(Tab::Sensors));
+        assert_eq!(Tab::from_digit('0'), None);
+        assert_eq!(Tab::from_digit('6'), None);
+    }
+
+    #[test]
+    fn clicks_map_to_padded_titles() {
+        let bar = TabBar {
+            area: Rect::new(0, 1, 80, 1),
+        };
+        // " 1 Overview " spans columns 0-11, then a divider at 12.
+        assert_eq!(bar.tab_at(0, 1), Some(Tab::Overview));
+        assert_eq!(bar.tab_at(11, 1), Some(Tab::Overview));
+        assert_eq!(bar.tab_at(13, 1), Some(Tab::Processes));
+        assert_eq!(bar.tab_at(5, 0), None);
+        assert_eq!(bar.tab_at(79, 1), None);
+    }
+}
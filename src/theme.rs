@@ -0,0 +1,71 @@
+//! Colors used across panels.
+//!
+//! Like [`crate::format`], the theme is set once at startup with [`init`] and read
+//! through [`current`]; before that (and in tests) [`Theme::default`] applies.
+
+use std::sync::OnceLock;
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Theme {
+    /// Border and title of the CPU and memory panels.
+    pub border: Color,
+    /// Usage and temperature levels: comfortable, getting busy, near the limit.
+    pub low: Color,
+    pub medium: Color,
+    pub high: Color,
+    pub cpu: Color,
+    pub memory: Color,
+    pub rx: Color,
+    pub tx: Color,
+    pub read: Color,
+    pub write: Color,
+    /// Background of the selected process row.
+    pub selection: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border: Color::Red,
+            low: Color::Green,
+            medium: Color::Yellow,
+            high: Color::Red,
+            cpu: Color::Cyan,
+            memory: Color::Green,
+            rx: Color::Green,
+            tx: Color::Blue,
+            read: Color::Green,
+            write: Color::Magenta,
+            selection: Color::Blue,
+        }
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Set the process-wide theme. Only the first call has any effect.
+pub(crate) fn init(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+pub(crate) fn current() -> Theme {
+    THEME.get().copied().unwrap_or_default()
+}
+
+/// Drop every color from a rendered frame for `--color never`. The selected process row
+/// is recognised by its background and shown reversed so it stays visible.
+pub(crate) fn strip_colors(buffer: &mut Buffer) {
+    let selection = current().selection;
+    for cell in &mut buffer.content {
+        if cell.bg == selection && selection != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}